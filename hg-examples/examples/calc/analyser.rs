@@ -1,6 +1,7 @@
 use crate::ast::{Add, Div, Expression, Mult, Number, Sub};
+use hg::diagnostics::{Diagnostic, Label, ToDiagnostic};
 use hg::metadata::Metadata;
-use hg::token::{Ascii, Token};
+use hg::token::{Ascii, Sign, Token};
 use hg::tree::{Node, Verse};
 
 #[derive(Debug, thiserror::Error)]
@@ -9,7 +10,7 @@ pub enum Error {
     UnexpectedNode(Metadata),
 
     #[error("invalid 64-bit signed integer {0} at {1}")]
-    InvalidInteger(u128, Metadata),
+    InvalidInteger(String, Metadata),
 
     #[error("unexpected symbol '{0}' at {1}")]
     UnexpectedSymbol(Ascii, Metadata),
@@ -30,6 +31,37 @@ pub enum Error {
     UnexpectedCommaSeparator
 }
 
+impl ToDiagnostic for Error {
+    fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            Error::UnexpectedNode(metadata) => {
+                Diagnostic::error("unexpected node")
+                    .with_label(Label::primary(metadata.clone(), "expected a number or an operator"))
+            }
+            Error::InvalidInteger(integer, metadata) => {
+                Diagnostic::error(format!("invalid 64-bit signed integer {integer}"))
+                    .with_label(Label::primary(metadata.clone(), "out of range"))
+            }
+            Error::UnexpectedSymbol(ascii, metadata) => {
+                Diagnostic::error(format!("unexpected symbol '{ascii}'"))
+                    .with_label(Label::primary(metadata.clone(), "unsupported operator"))
+                    .with_note("supported operators are '+', '-', '*' and '/'")
+            }
+            Error::StrayOperator(ascii, metadata) => {
+                Diagnostic::error(format!("stray operator '{ascii}'"))
+                    .with_label(Label::primary(metadata.clone(), "missing an operand"))
+            }
+            Error::StrayExpression(metadata) => {
+                Diagnostic::error("stray expression")
+                    .with_label(Label::primary(metadata.clone(), "missing an operator"))
+            }
+            Error::NoExpression | Error::UnexpectedLineSeparator | Error::UnexpectedCommaSeparator => {
+                Diagnostic::error(self.to_string())
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 enum Element {
     Expression(Expression, Metadata),
@@ -92,19 +124,22 @@ fn process_elements<'a, I: Iterator<Item = Node<'a>>>(
 ) -> impl Iterator<Item = Result<Element, Error>> {
     iter.map(|node| {
         let element = match node {
-            Node::Raw(Token::Integer(uint), metadata) => {
-                let (eval, metadata) = convert_integer(uint, metadata)?;
+            Node::Raw(
+                token @ (Token::Integer(_) | Token::RadixInteger(_, _) | Token::BigInteger(_) | Token::Decimal(_) | Token::BigDecimal(_) | Token::Scientific(_)),
+                metadata,
+            ) => {
+                let (eval, metadata) = convert_number(Sign::Plus, token, metadata)?;
+                Element::Expression(eval, metadata)
+            }
+            Node::Raw(Token::Signed(sign, token), metadata) => {
+                let (eval, metadata) = convert_number(sign, *token, metadata)?;
                 Element::Expression(eval, metadata)
             }
-            Node::Raw(Token::Decimal(decimal), metadata) => Element::Expression(
-                Expression::from(Number::Float(f64::from(decimal))),
-                metadata,
-            ),
             Node::Raw(Token::Symbol(Ascii(byte)), metadata) => match byte {
                 b'+' | b'-' | b'*' | b'/' => Element::Operator(Ascii(byte), metadata),
                 _ => Err(Error::UnexpectedSymbol(Ascii(byte), metadata))?,
             },
-            Node::List(verses, metadata) => {
+            Node::List(_, verses, metadata) => {
                 let list_elements = process_elements(flatten(verses)?);
                 let folded = fold_elements(list_elements)?;
                 Element::Expression(folded, metadata)
@@ -145,7 +180,11 @@ fn fold_elements<I: Iterator<Item = Result<Element, Error>>>(iter: I) -> Result<
 }
 
 fn fold_prefix_sub<I: IntoIterator<Item = Element>>(elements: I) -> Result<Vec<Element>, Error> {
-    fold_prefix(elements, |rhs| Expression::from(Sub(Box::new(Expression::from(Number::Integer(0))), rhs)), b'-')
+    fold_prefix(elements, negate, b'-')
+}
+
+fn negate(expr: Box<Expression>) -> Expression {
+    Expression::from(Sub(Box::new(Expression::from(Number::Integer(0))), expr))
 }
 
 fn fold_div<I: IntoIterator<Item = Element>>(elements: I) -> Result<Vec<Element>, Error> {
@@ -278,10 +317,39 @@ fn take_last<T>(vec: &mut Vec<T>) -> Option<T> {
     }
 }
 
-fn convert_integer(uint: u128, metadata: Metadata) -> Result<(Expression, Metadata), Error> {
-    match i64::try_from(uint) {
-        Ok(int) => Ok((Expression::from(Number::Integer(int)), metadata)),
-        Err(_) => Err(Error::InvalidInteger(uint, metadata))?,
+fn convert_number(sign: Sign, token: Token, metadata: Metadata) -> Result<(Expression, Metadata), Error> {
+    let number = match token {
+        Token::Integer(uint) | Token::RadixInteger(uint, _) => return convert_integer(sign, uint, metadata),
+        Token::BigInteger(integer) => {
+            let sign = if sign == Sign::Minus { "-" } else { "" };
+            return Err(Error::InvalidInteger(format!("{sign}{integer}"), metadata))
+        }
+        Token::Decimal(decimal) => Number::Decimal(decimal),
+        Token::BigDecimal(decimal) => match f64::try_from(&decimal) {
+            Ok(float) => Number::Float(float),
+            Err(_) => return Err(Error::UnexpectedNode(metadata)),
+        },
+        Token::Scientific(scientific) => Number::Float(f64::from(scientific)),
+        _ => return Err(Error::UnexpectedNode(metadata)),
+    };
+    let expr = Expression::from(number);
+    match sign {
+        Sign::Plus => Ok((expr, metadata)),
+        Sign::Minus => Ok((negate(Box::new(expr)), metadata)),
+    }
+}
+
+fn convert_integer(sign: Sign, uint: u128, metadata: Metadata) -> Result<(Expression, Metadata), Error> {
+    let int = match sign {
+        Sign::Plus => i64::try_from(uint).ok(),
+        Sign::Minus => i128::try_from(uint).ok().and_then(|int| i64::try_from(-int).ok()),
+    };
+    match int {
+        Some(int) => Ok((Expression::from(Number::Integer(int)), metadata)),
+        None => {
+            let sign = if sign == Sign::Minus { "-" } else { "" };
+            Err(Error::InvalidInteger(format!("{sign}{uint}"), metadata))?
+        }
     }
 }
 
@@ -1,7 +1,8 @@
 use crate::analyser::{Element, Error, analyse, fold_mult, take_last, flatten};
 use crate::ast::{Eval, Expression, Mult, Number};
-use hg::lexer::Tokeniser;
-use hg::metadata::{Location, Metadata};
+use hg::diagnostics::{Options as DiagnosticOptions, ToDiagnostic};
+use hg::lexer::{Options, Tokeniser};
+use hg::metadata::Metadata;
 use hg::parser::parse;
 use hg::symbols::SymbolTable;
 use hg::token::{Ascii, Token};
@@ -35,16 +36,7 @@ impl From<u8> for Element {
 
 fn with_metadata<I: IntoIterator<Item = Element>>(elements: I) -> impl Iterator<Item = Element> {
     elements.into_iter().enumerate().map(|(index, element)| {
-        let metadata = Metadata {
-            start: Some(Location {
-                line: 1,
-                column: (index * 2 + 1) as u32,
-            }),
-            end: Some(Location {
-                line: 1,
-                column: (index * 2 + 2) as u32,
-            }),
-        };
+        let metadata = Metadata::bounds(1, (index * 2 + 1) as u32, index * 2, 1, (index * 2 + 2) as u32, index * 2 + 2);
         match element {
             Element::Expression(expression, _) => Element::Expression(expression, metadata),
             Element::Operator(ascii, _) => Element::Operator(ascii, metadata),
@@ -142,12 +134,12 @@ fn fold_mult_with_trailing_sum() {
                     Box::new(Expression::from(Number::Integer(3))),
                     Box::new(Expression::from(Number::Integer(4)))
                 )),
-                metadata_bounds(1, 1, 1, 6)
+                metadata_bounds(1, 1, 0, 1, 6, 6)
             ),
-            Element::Operator(Ascii(b'+'), metadata_bounds(1, 7, 1, 8)),
+            Element::Operator(Ascii(b'+'), metadata_bounds(1, 7, 6, 1, 8, 8)),
             Element::Expression(
                 Expression::from(Number::Integer(5)),
-                metadata_bounds(1, 9, 1, 10)
+                metadata_bounds(1, 9, 8, 1, 10, 10)
             )
         ],
         folded
@@ -173,15 +165,15 @@ fn fold_mult_with_mid_sum() {
                     Box::new(Expression::from(Number::Integer(3))),
                     Box::new(Expression::from(Number::Integer(4)))
                 )),
-                metadata_bounds(1, 1, 1, 6)
+                metadata_bounds(1, 1, 0, 1, 6, 6)
             ),
-            Element::Operator(Ascii(b'+'), metadata_bounds(1, 7, 1, 8)),
+            Element::Operator(Ascii(b'+'), metadata_bounds(1, 7, 6, 1, 8, 8)),
             Element::Expression(
                 Expression::from(Mult(
                     Box::new(Expression::from(Number::Integer(5))),
                     Box::new(Expression::from(Number::Integer(6)))
                 )),
-                metadata_bounds(1, 9, 1, 14)
+                metadata_bounds(1, 9, 8, 1, 14, 14)
             ),
         ],
         folded
@@ -215,7 +207,11 @@ fn fold_mult_stray_mid_operator_err() {
 }
 
 fn evaluate(str: &'static str) -> Result<f64, Box<dyn std::error::Error>> {
-    let tok = Tokeniser::new(str, SymbolTable::default());
+    let options = Options {
+        signed_numbers: true,
+        ..Options::default()
+    };
+    let tok = Tokeniser::with_options(str, SymbolTable::default(), options);
     let root = parse(tok)?.ok_or(Error::NoExpression)?;
     let expr = analyse(root)?;
     Ok(expr.eval())
@@ -274,6 +270,16 @@ fn lex_parse_analyse() {
         ("16 / (4 + 4) - 3", -1.0),
         ("16 / (4 + 6 - 2)", 2.0),
         ("16 / -(4 + 6 - 2)", -2.0),
+        ("1.5", 1.5),
+        ("0.1 + 0.2", 0.3),
+        ("0.3 - 0.1", 0.2),
+        ("1.1 * 1.1", 1.21),
+        ("2 * 0.05 + 1", 1.1),
+        ("-0.5 + 1", 0.5),
+        ("1.5 / 0.5", 3.0),
+        ("2 * -0.5", -1.0),
+        ("-1e2 - +1", -101.0),
+        ("-9_223_372_036_854_775_808", -9_223_372_036_854_775_808.0),
     ] {
         let actual = evaluate(input);
         match actual {
@@ -293,11 +299,14 @@ fn lex_parse_analyse_err() {
         ("", "no expression"),
         ("()", "no expression"),
         ("9_223_372_036_854_775_808", "invalid 64-bit signed integer 9223372036854775808 at line 1, columns 1 to 25"),
+        ("-9_223_372_036_854_775_809", "invalid 64-bit signed integer -9223372036854775809 at line 1, columns 1 to 26"),
+        ("340282366920938463463374607431768211456", "invalid 64-bit signed integer 340282366920938463463374607431768211456 at line 1, columns 1 to 39"),
+        ("1 - -0x1_0000_0000_0000_0000_0000_0000_0000_0000", "invalid 64-bit signed integer -0x100000000000000000000000000000000 at line 1, columns 5 to 48"),
         ("(z)", "unexpected node at line 1, column 2"),
         ("+", "stray operator '+' at line 1, column 1"),
         ("1 1", "stray expression at line 1, column 3"),
         ("1 + + 1", "stray operator '+' at line 1, column 5"),
-        ("1, 2", "unexpected token Symbol(Ascii(b','))"),
+        ("1, 2", "unexpected token Symbol(Ascii(b',')) at line 1, column 2"),
         ("1 ^ 2", "unexpected symbol '^' at line 1, column 3"),
         ("1:2", "unexpected node at line 1, columns 1 to 3"),
         ("1 + (2,3)", "unexpected comma separator"),
@@ -314,3 +323,19 @@ fn lex_parse_analyse_err() {
         }
     }
 }
+
+#[test]
+fn render_diagnostic() {
+    let str = "1 + 2 ^ 3";
+    let root = parse(Tokeniser::new(str, SymbolTable::default())).unwrap().unwrap();
+    let err = analyse(root).unwrap_err();
+    assert_eq!("\
+error: unexpected symbol '^'
+ --> line 1, column 7
+  |
+1 | 1 + 2 ^ 3
+  |       ^ unsupported operator
+  |
+  = note: supported operators are '+', '-', '*' and '/'
+", err.to_diagnostic().render(str, &DiagnosticOptions::default()));
+}
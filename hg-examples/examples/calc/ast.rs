@@ -1,3 +1,4 @@
+use hg::token::Decimal;
 use std::fmt::Debug;
 
 pub trait Eval: Debug {
@@ -13,16 +14,36 @@ pub enum Expression {
     Number(Number)
 }
 
+impl Expression {
+    /// Evaluates the expression in a single pass, yielding its value along with its value
+    /// without rounding. The latter is only available if the expression comprises additions,
+    /// subtractions and multiplications of non-negative numbers, and no intermediate result is
+    /// negative. The value of each subexpression is exact where possible, and rounded otherwise.
+    fn evaluate(&self) -> (f64, Option<Decimal>) {
+        let (value, exact) = match self {
+            Expression::Add(Add(lhs, rhs)) => {
+                let ((lhs, lhs_exact), (rhs, rhs_exact)) = (lhs.evaluate(), rhs.evaluate());
+                (lhs + rhs, lhs_exact.zip(rhs_exact).and_then(|(lhs, rhs)| lhs.checked_add(rhs)))
+            }
+            Expression::Sub(Sub(lhs, rhs)) => {
+                let ((lhs, lhs_exact), (rhs, rhs_exact)) = (lhs.evaluate(), rhs.evaluate());
+                (lhs - rhs, lhs_exact.zip(rhs_exact).and_then(|(lhs, rhs)| lhs.checked_sub(rhs)))
+            }
+            Expression::Mult(Mult(lhs, rhs)) => {
+                let ((lhs, lhs_exact), (rhs, rhs_exact)) = (lhs.evaluate(), rhs.evaluate());
+                (lhs * rhs, lhs_exact.zip(rhs_exact).and_then(|(lhs, rhs)| lhs.checked_mul(rhs)))
+            }
+            Expression::Div(Div(lhs, rhs)) => (lhs.evaluate().0 / rhs.evaluate().0, None),
+            Expression::Number(number) => (number.eval(), number.as_decimal())
+        };
+        (exact.map_or(value, f64::from), exact)
+    }
+}
+
 impl Eval for Expression {
     #[inline]
     fn eval(&self) -> f64 {
-        match self {
-            Expression::Add(eval) => eval.eval(),
-            Expression::Sub(eval) => eval.eval(),
-            Expression::Mult(eval) => eval.eval(),
-            Expression::Div(eval) => eval.eval(),
-            Expression::Number(eval) => eval.eval()
-        }
+        self.evaluate().0
     }
 }
 
@@ -62,7 +83,7 @@ pub struct Add(pub Box<Expression>, pub Box<Expression>);
 impl Eval for Add {
     #[inline]
     fn eval(&self) -> f64 {
-        &self.0.eval() + &self.1.eval()
+        self.0.eval() + self.1.eval()
     }
 }
 
@@ -72,7 +93,7 @@ pub struct Sub(pub Box<Expression>, pub Box<Expression>);
 impl Eval for Sub {
     #[inline]
     fn eval(&self) -> f64 {
-        &self.0.eval() - &self.1.eval()
+        self.0.eval() - self.1.eval()
     }
 }
 
@@ -82,7 +103,7 @@ pub struct Mult(pub Box<Expression>, pub Box<Expression>);
 impl Eval for Mult {
     #[inline]
     fn eval(&self) -> f64 {
-        &self.0.eval() * &self.1.eval()
+        self.0.eval() * self.1.eval()
     }
 }
 
@@ -92,21 +113,33 @@ pub struct Div(pub Box<Expression>, pub Box<Expression>);
 impl Eval for Div {
     #[inline]
     fn eval(&self) -> f64 {
-        &self.0.eval() / &self.1.eval()
+        self.0.eval() / self.1.eval()
     }
 }
 
 #[derive(Debug, PartialEq)]
 pub enum Number {
     Integer(i64),
+    Decimal(Decimal),
     Float(f64)
 }
 
+impl Number {
+    fn as_decimal(&self) -> Option<Decimal> {
+        match *self {
+            Number::Integer(i) => u128::try_from(i).ok().map(|i| Decimal(i, 0, 0)),
+            Number::Decimal(d) => Some(d),
+            Number::Float(_) => None
+        }
+    }
+}
+
 impl Eval for Number {
     #[inline]
     fn eval(&self) -> f64 {
         match *self {
             Number::Integer(i) => i as f64,
+            Number::Decimal(d) => f64::from(d),
             Number::Float(f) => f
         }
     }
@@ -1,6 +1,8 @@
 use crate::analyser::analyse;
 use crate::ast::Eval;
-use hg::lexer::Tokeniser;
+use hg::diagnostics;
+use hg::diagnostics::ToDiagnostic;
+use hg::lexer::{Options, Tokeniser};
 use hg::parser::parse;
 use hg::symbols::SymbolTable;
 use std::error::Error;
@@ -10,11 +12,21 @@ mod ast;
 
 fn main() -> Result<(), Box<dyn Error>>{
     let str = "-1 * 2 + 4 + 3 * 2 + -5";
-    let tok = Tokeniser::new(str, SymbolTable::default());
-    let root = parse(tok)?;
-    let expr = analyse(root.unwrap())?;
+    let options = Options {
+        signed_numbers: true,
+        ..Options::default()
+    };
+    let tok = Tokeniser::with_options(str, SymbolTable::default(), options);
+    let root = parse(tok).map_err(|err| report(str, err))?;
+    let expr = analyse(root.unwrap()).map_err(|err| report(str, err))?;
     let eval = expr.eval();
     println!(">> {str}");
     println!("<< {eval}");
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Prints the diagnostic for an error against its source before propagating the error.
+fn report<E: ToDiagnostic>(source: &str, err: E) -> E {
+    eprint!("{}", err.to_diagnostic().render(source, &diagnostics::Options { colour: true }));
+    err
+}
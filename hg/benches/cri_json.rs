@@ -20,19 +20,13 @@ fn criterion_benchmark(c: &mut Criterion) {
             let data = std::hint::black_box(data);
             b.iter_with_setup(
                 || Tokeniser::new(data, SymbolTable::default()),
-                |iter| {
-                    let verse = parse(iter).unwrap();
-                    verse
-                },
+                |iter| parse(iter).unwrap(),
             );
         });
 
         c.bench_function(format!("cri_json_combined-{name}").as_str(), |b| {
             let data = std::hint::black_box(data);
-            b.iter(|| {
-                let verse = parse(Tokeniser::new(data, SymbolTable::default())).unwrap();
-                verse
-            });
+            b.iter(|| parse(Tokeniser::new(data, SymbolTable::default())).unwrap());
         });
     }
     bench_file(c, "canada");
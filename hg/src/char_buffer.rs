@@ -177,9 +177,9 @@ mod tests {
         assert!(matches!(buf.mode, Mode::Slice));
         assert!(buf.is_empty());
         assert_eq!(buf.len(), 0);
-        assert_eq!("", buf.as_str(&bytes));
-        assert_eq!("", buf.string(&bytes));
-        assert!(matches!(buf.string(&bytes), Cow::Borrowed(_)));
+        assert_eq!("", buf.as_str(bytes));
+        assert_eq!("", buf.string(bytes));
+        assert!(matches!(buf.string(bytes), Cow::Borrowed(_)));
     }
 
     #[test]
@@ -194,20 +194,20 @@ mod tests {
         buf.push_char(1, 'i');
         assert!(matches!(buf.mode, Mode::Slice));
         println!("buf: {buf:?}");
-        assert_eq!("hi", buf.as_str(&bytes));
-        assert_eq!("hi", buf.string(&bytes));
-        assert!(matches!(buf.string(&bytes), Cow::Borrowed(_)));
+        assert_eq!("hi", buf.as_str(bytes));
+        assert_eq!("hi", buf.string(bytes));
+        assert!(matches!(buf.string(bytes), Cow::Borrowed(_)));
 
         buf.clear();
         println!("buf: {buf:?}");
         assert!(buf.is_empty());
         assert_eq!(buf.len(), 0);
-        assert_eq!("", buf.as_str(&bytes));
+        assert_eq!("", buf.as_str(bytes));
 
         buf.push_char(2, 'µ');
         buf.push_char(4, '\n');
         println!("buf: {buf:?}");
-        assert_eq!("µ\n", buf.as_str(&bytes));
+        assert_eq!("µ\n", buf.as_str(bytes));
     }
 
     #[test]
@@ -219,50 +219,50 @@ mod tests {
         buf.push_char(0, 'h');
         buf.push_char(1, 'i');
         println!("buf: {buf:?}");
-        buf.copy(&bytes);
+        buf.copy(bytes);
         assert!(!buf.is_empty());
         assert_eq!(buf.len(), 2);
         assert!(matches!(buf.mode, Mode::Copy));
-        assert_eq!("hi", buf.as_str(&bytes));
-        assert_eq!("hi", buf.string(&bytes));
-        assert!(matches!(buf.string(&bytes), Cow::Owned(_)));
+        assert_eq!("hi", buf.as_str(bytes));
+        assert_eq!("hi", buf.string(bytes));
+        assert!(matches!(buf.string(bytes), Cow::Owned(_)));
 
         buf.push_char(0, 'µ');
         buf.push_char(0, '\n');
-        assert_eq!("hiµ\n", buf.as_str(&bytes));
-        assert_eq!("hiµ\n", buf.string(&bytes));
-        assert!(matches!(buf.string(&bytes), Cow::Owned(_)));
+        assert_eq!("hiµ\n", buf.as_str(bytes));
+        assert_eq!("hiµ\n", buf.string(bytes));
+        assert!(matches!(buf.string(bytes), Cow::Owned(_)));
 
         buf.clear();
         assert!(buf.is_empty());
         assert_eq!(buf.len(), 0);
         assert!(matches!(buf.mode, Mode::Slice));
-        assert_eq!("", buf.as_str(&bytes));
-        assert_eq!("", buf.string(&bytes));
-        assert!(matches!(buf.string(&bytes), Cow::Borrowed(_)));
+        assert_eq!("", buf.as_str(bytes));
+        assert_eq!("", buf.string(bytes));
+        assert!(matches!(buf.string(bytes), Cow::Borrowed(_)));
 
         buf.push_char(5, 'h');
         buf.push_char(6, 'e');
         assert!(matches!(buf.mode, Mode::Slice));
-        assert_eq!("he", buf.as_str(&bytes));
+        assert_eq!("he", buf.as_str(bytes));
 
-        buf.copy(&bytes);
+        buf.copy(bytes);
         assert_eq!(0, buf.offset);
         assert_eq!(0, buf.len);
         assert!(matches!(buf.mode, Mode::Copy));
-        assert_eq!("he", buf.as_str(&bytes));
+        assert_eq!("he", buf.as_str(bytes));
 
         buf.push_char(0, 'l');
         assert!(matches!(buf.mode, Mode::Copy));
-        assert_eq!("hel", buf.as_str(&bytes));
+        assert_eq!("hel", buf.as_str(bytes));
 
         buf.clear();
-        assert_eq!("", buf.as_str(&bytes));
+        assert_eq!("", buf.as_str(bytes));
 
         buf.push_char(2, 'µ');
         buf.push_char(4, '\n');
         assert!(matches!(buf.mode, Mode::Slice));
-        assert_eq!("µ\n", buf.as_str(&bytes));
+        assert_eq!("µ\n", buf.as_str(bytes));
     }
 
     #[test]
@@ -0,0 +1,603 @@
+//! Rendering of errors against the source text they arose from, in the style of rustc: the
+//! offending lines are shown with the spans of interest underlined and labelled, followed by any
+//! notes.
+
+use std::fmt::{Display, Formatter};
+use crate::lexer;
+use crate::metadata::{Bound, Location, Metadata};
+use crate::newline_terminated_bytes::NewlineTerminatedBytes;
+use crate::parser;
+use crate::parser::Suggestion;
+
+/// The number of lines a multi-line label may span before the lines between its first and last
+/// are elided.
+const MAX_SPANNED_LINES: u32 = 4;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+impl Severity {
+    #[inline]
+    fn colour(self) -> &'static str {
+        match self {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+            Severity::Note => GREEN,
+            Severity::Help => CYAN,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Note => write!(f, "note"),
+            Severity::Help => write!(f, "help"),
+        }
+    }
+}
+
+/// A span of the source, underlined with carets if primary or dashes otherwise, and annotated
+/// with a (possibly empty) message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub metadata: Metadata,
+    pub message: String,
+    pub primary: bool,
+}
+
+impl Label {
+    #[inline]
+    pub fn primary(metadata: Metadata, message: impl Into<String>) -> Self {
+        Self { metadata, message: message.into(), primary: true }
+    }
+
+    #[inline]
+    pub fn secondary(metadata: Metadata, message: impl Into<String>) -> Self {
+        Self { metadata, message: message.into(), primary: false }
+    }
+}
+
+/// A report of a problem in the source, comprising a headline message, labelled spans and
+/// trailing notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<(Severity, String)>,
+}
+
+impl Diagnostic {
+    #[inline]
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self { severity, message: message.into(), labels: vec![], notes: vec![] }
+    }
+
+    #[inline]
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    #[inline]
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    #[inline]
+    pub fn with_note(mut self, message: impl Into<String>) -> Self {
+        self.notes.push((Severity::Note, message.into()));
+        self
+    }
+
+    #[inline]
+    pub fn with_help(mut self, message: impl Into<String>) -> Self {
+        self.notes.push((Severity::Help, message.into()));
+        self
+    }
+
+    /// Renders the diagnostic against the source it refers to. Every line of the output,
+    /// including the last, ends with a line break. Labels with unspecified metadata are rendered
+    /// as notes.
+    pub fn render(&self, source: &str, options: &Options) -> String {
+        let paint = Paint(options.colour);
+        let (starts, lines): (Vec<_>, Vec<_>) = split_lines(source).into_iter().unzip();
+        let mut regions = vec![];
+        let mut notes = vec![];
+        for label in &self.labels {
+            match Region::resolve(&label.metadata, source, &starts, &lines) {
+                Some(region) => regions.push((region, label)),
+                None if !label.message.is_empty() => notes.push((Severity::Note, label.message.clone())),
+                None => {}
+            }
+        }
+        notes.extend(self.notes.iter().cloned());
+
+        let mut shown = vec![];
+        for (region, _) in &regions {
+            if region.end.line - region.start.line < MAX_SPANNED_LINES {
+                shown.extend(region.start.line..=region.end.line);
+            } else {
+                shown.extend([region.start.line, region.start.line + 1, region.end.line - 1, region.end.line]);
+            }
+        }
+        shown.sort_unstable();
+        shown.dedup();
+        let width = shown.last().map_or(1, |line| line.to_string().len());
+        let pad = " ".repeat(width);
+
+        let mut buf = String::new();
+        buf.push_str(&paint.bold(self.severity.colour(), &self.severity.to_string()));
+        buf.push_str(&paint.bold("", &format!(": {}", self.message)));
+        buf.push('\n');
+        let location = self.labels.iter()
+            .filter(|label| label.primary)
+            .chain(&self.labels)
+            .map(|label| &label.metadata)
+            .find(|metadata| metadata.start.is_some() || metadata.end.is_some());
+        if let Some(metadata) = location {
+            let location = match (&metadata.start, &metadata.end) {
+                (Some(location), None) | (None, Some(location)) => location.to_string(),
+                _ => metadata.to_string()
+            };
+            buf.push_str(&format!("{pad}{} {location}\n", paint.bold(BLUE, "-->")));
+        }
+
+        if !shown.is_empty() {
+            let gutter = paint.bold(BLUE, "|");
+            buf.push_str(&format!("{pad} {gutter}\n"));
+            let mut previous = None;
+            for &number in &shown {
+                if let Some(previous) = previous && number > previous + 1 {
+                    buf.push_str(&paint.bold(BLUE, "..."));
+                    buf.push('\n');
+                }
+                previous = Some(number);
+                let line = lines.get(number as usize - 1).copied().unwrap_or_default();
+                buf.push_str(&format!("{} {gutter} {line}", paint.bold(BLUE, &format!("{number:>width$}"))));
+                buf.push('\n');
+
+                let mut underlines: Vec<_> = regions.iter()
+                    .filter_map(|(region, label)| region.columns(number, line).map(|columns| (columns, region, label)))
+                    .collect();
+                underlines.sort_by_key(|((start, _), _, _)| *start);
+                for ((start, end), region, label) in underlines {
+                    let indent: String = line.chars()
+                        .chain(std::iter::repeat(' '))
+                        .take(start as usize - 1)
+                        .map(|char| if char == '\t' { '\t' } else { ' ' })
+                        .collect();
+                    let (mark, colour) = if label.primary { ('^', self.severity.colour()) } else { ('-', BLUE) };
+                    let mut underline = mark.to_string().repeat((end - start + 1) as usize);
+                    if number == region.end.line && !label.message.is_empty() {
+                        underline.push(' ');
+                        underline.push_str(&label.message);
+                    }
+                    buf.push_str(&format!("{pad} {gutter} {indent}{}\n", paint.bold(colour, &underline)));
+                }
+            }
+            if !notes.is_empty() {
+                buf.push_str(&format!("{pad} {gutter}\n"));
+            }
+        }
+        for (severity, note) in notes {
+            buf.push_str(&format!("{pad} {} {}: {note}\n", paint.bold(BLUE, "="), paint.bold("", &severity.to_string())));
+        }
+        buf
+    }
+}
+
+/// Rendering options for a [`Diagnostic`].
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Whether to colour the output with ANSI escape sequences.
+    pub colour: bool,
+}
+
+/// Conversion of an error into a [`Diagnostic`], for rendering against its source.
+pub trait ToDiagnostic {
+    fn to_diagnostic(&self) -> Diagnostic;
+}
+
+impl ToDiagnostic for lexer::Error {
+    fn to_diagnostic(&self) -> Diagnostic {
+        let at = |location: &Location| Metadata { start: None, end: Some(location.clone()) };
+        match self {
+            lexer::Error::Io(err) => Diagnostic::error(format!("i/o error {err}")),
+            lexer::Error::UnexpectedCharacter(char, location) => {
+                Diagnostic::error(format!("unexpected character '{char}'"))
+                    .with_label(Label::primary(at(location), "unexpected character"))
+            }
+            lexer::Error::UnterminatedLiteral(location) => {
+                Diagnostic::error("unterminated literal")
+                    .with_label(Label::primary(at(location), "literal ends here"))
+            }
+            lexer::Error::UnknownEscapeSequence(sequence, location) => {
+                Diagnostic::error(format!("unknown escape sequence \"{sequence}\""))
+                    .with_label(Label::primary(at(location), "unknown escape sequence"))
+            }
+            lexer::Error::InvalidCodepoint(sequence, err, location) => {
+                Diagnostic::error(format!("invalid codepoint \"{sequence}\""))
+                    .with_label(Label::primary(at(location), err.to_string()))
+            }
+            lexer::Error::UnpairedSurrogate(sequence, location) => {
+                Diagnostic::error(format!("unpaired surrogate \"{sequence}\""))
+                    .with_label(Label::primary(at(location), "unpaired surrogate"))
+            }
+            lexer::Error::UnparsableInteger(integer, err, location) => {
+                Diagnostic::error(format!("unparsable integer {integer}"))
+                    .with_label(Label::primary(at(location), err.to_string()))
+            }
+            lexer::Error::UnparsableDecimal(whole, fractional, err, location) => {
+                Diagnostic::error(format!("unparsable decimal {whole}.{fractional}"))
+                    .with_label(Label::primary(at(location), err.to_string()))
+            }
+            lexer::Error::ExponentTooLarge(exponent, location) => {
+                Diagnostic::error(format!("exponent {exponent} too large"))
+                    .with_label(Label::primary(at(location), "exponent too large"))
+                    .with_note(format!("exponents are limited to ±{}", lexer::MAX_EXPONENT))
+            }
+            lexer::Error::UnparsableExponent(exponent, err, location) => {
+                Diagnostic::error(format!("unparsable exponent {exponent}"))
+                    .with_label(Label::primary(at(location), err.to_string()))
+            }
+            lexer::Error::UnterminatedRawLiteral(closer, location) => {
+                Diagnostic::error("unterminated raw literal")
+                    .with_label(Label::primary(at(location), "literal ends here"))
+                    .with_note(format!("expected {closer}"))
+            }
+            lexer::Error::EmptyCharacterLiteral(location) => {
+                Diagnostic::error("empty character literal")
+                    .with_label(Label::primary(at(location), "empty character literal"))
+            }
+            lexer::Error::UnterminatedComment(location) => {
+                Diagnostic::error("unterminated comment")
+                    .with_label(Label::primary(at(location), "comment opens here"))
+            }
+            lexer::Error::InvalidCommentDelimiter(delimiter, reason) => {
+                Diagnostic::error(format!("invalid comment delimiter \"{delimiter}\""))
+                    .with_note(reason.to_string())
+            }
+        }
+    }
+}
+
+impl ToDiagnostic for parser::Error<'_> {
+    fn to_diagnostic(&self) -> Diagnostic {
+        match self {
+            parser::Error::Lexer(err) => err.to_diagnostic(),
+            parser::Error::UnterminatedContainer => Diagnostic::error("unterminated container"),
+            parser::Error::UnterminatedList(metadata) => {
+                Diagnostic::error("unterminated list")
+                    .with_label(Label::primary(metadata.clone(), "unclosed delimiter"))
+            }
+            parser::Error::UnterminatedRelation(metadata) => {
+                Diagnostic::error("unterminated relation")
+                    .with_label(Label::primary(metadata.clone(), "relation separated here"))
+            }
+            parser::Error::UnterminatedPrefix => Diagnostic::error("unterminated prefix"),
+            parser::Error::UnterminatedPhrase(metadata) => {
+                Diagnostic::error("unterminated phrase")
+                    .with_label(Label::primary(metadata.clone(), "phrase not ended by a line break"))
+            }
+            parser::Error::UnterminatedInterpolation(metadata) => {
+                Diagnostic::error("unterminated interpolation")
+                    .with_label(Label::primary(metadata.clone(), "unclosed interpolation"))
+            }
+            parser::Error::UnexpectedToken(token, metadata) => {
+                Diagnostic::error(format!("unexpected token {token:?}"))
+                    .with_label(Label::primary(metadata.clone(), "unexpected token"))
+            }
+            parser::Error::MismatchedDelimiter(open, open_metadata, close, close_metadata, suggestion) => {
+                let help = match suggestion {
+                    Suggestion::Insert(delimiter) => format!("insert a '{}' to close the list opened by '{}'", delimiter.right(), open.left()),
+                    Suggestion::Replace(delimiter) => format!("replace '{}' with '{}'", close.right(), delimiter.right()),
+                };
+                Diagnostic::error(format!("mismatched closing delimiter '{}'", close.right()))
+                    .with_label(Label::primary(close_metadata.as_ref().clone(), "mismatched closing delimiter"))
+                    .with_label(Label::secondary(open_metadata.as_ref().clone(), "unclosed delimiter"))
+                    .with_help(help)
+            }
+            parser::Error::EmptyVerse(metadata) => {
+                Diagnostic::error("empty verse")
+                    .with_label(Label::primary(metadata.clone(), "separator follows nothing"))
+            }
+            parser::Error::EmptyRelationSegment(metadata) => {
+                Diagnostic::error("empty relation segment")
+                    .with_label(Label::primary(metadata.clone(), "separator lacks a segment"))
+            }
+            parser::Error::EmptyEmbed(metadata) => {
+                Diagnostic::error("empty embedded expression")
+                    .with_label(Label::primary(metadata.clone(), "expected an expression"))
+            }
+        }
+    }
+}
+
+/// Splits the source into lines at every line terminator, as the tokeniser does, pairing the text
+/// of each line with its offset.
+fn split_lines(source: &str) -> Vec<(usize, &str)> {
+    let mut lines = vec![];
+    let mut bytes = NewlineTerminatedBytes::unterminated(source.bytes());
+    let mut start = 0;
+    while let Some((index, byte)) = bytes.next() {
+        if byte == b'\n' {
+            lines.push((start, &source[start..index]));
+            start = bytes.offset();
+        }
+    }
+    if start < source.len() {
+        lines.push((start, &source[start..]));
+    }
+    lines
+}
+
+/// A line and column, both 1-based, where the column is counted in characters.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    line: u32,
+    column: u32,
+}
+
+impl Position {
+    /// Locates the character at the given location by its byte offset, which is its raw position
+    /// regardless of the unit its column is counted in or the width of tabs. Falls back on its
+    /// column if the offset does not lie on a character boundary.
+    fn locate(location: &Location, bound: Bound, source: &str, starts: &[usize]) -> Self {
+        let line = location.line.max(1);
+        let start = starts.get(line as usize - 1).copied().unwrap_or(source.len());
+        let end = starts.get(line as usize).copied().unwrap_or(source.len()).max(start);
+        let column = match source.get(start..location.offset.clamp(start, end)) {
+            Some(text) => text.chars().count() as u32 + u32::from(bound == Bound::Start),
+            None => location.column
+        };
+        Self { line, column }
+    }
+}
+
+/// The region of a label, resolved against the source lines to inclusive positions.
+#[derive(Debug)]
+struct Region {
+    start: Position,
+    end: Position,
+}
+
+impl Region {
+    /// Resolves the given metadata, or returns `None` if it is unspecified. An open-ended region
+    /// resolves to the character at its specified location.
+    fn resolve(metadata: &Metadata, source: &str, starts: &[usize], lines: &[&str]) -> Option<Self> {
+        let (start, end) = match (&metadata.start, &metadata.end) {
+            (None, None) => return None,
+            (Some(start), Some(end)) => {
+                (Position::locate(start, Bound::Start, source, starts), Position::locate(end, Bound::End, source, starts))
+            }
+            (Some(start), None) => {
+                let position = Position::locate(start, Bound::Start, source, starts);
+                (position, position)
+            }
+            (None, Some(end)) => {
+                let position = Position::locate(end, Bound::End, source, starts);
+                (position, position)
+            }
+        };
+        let start = Position { line: start.line, column: start.column.max(1) };
+        let end = if end.column == 0 && end.line > start.line {
+            // ends before the first character of its line, i.e., at the end of the previous one
+            let line = end.line - 1;
+            let len = lines.get(line as usize - 1).map_or(0, |line| line.chars().count()) as u32;
+            Position { line, column: len.max(1) }
+        } else {
+            Position { line: end.line.max(start.line), column: end.column.max(1) }
+        };
+        let end = if end.line == start.line {
+            Position { line: end.line, column: end.column.max(start.column) }
+        } else {
+            end
+        };
+        Some(Self { start, end })
+    }
+
+    /// The inclusive range of columns underlined on the given line, if the region covers it.
+    fn columns(&self, number: u32, line: &str) -> Option<(u32, u32)> {
+        if number < self.start.line || number > self.end.line {
+            return None
+        }
+        let len = (line.chars().count() as u32).max(1);
+        let start = if number == self.start.line { self.start.column } else { 1 };
+        let end = if number == self.end.line { self.end.column } else { len.max(start) };
+        Some((start, end))
+    }
+}
+
+const RED: &str = "31";
+const GREEN: &str = "32";
+const YELLOW: &str = "33";
+const BLUE: &str = "34";
+const CYAN: &str = "36";
+
+/// Emboldens and colours text with ANSI escape sequences, if enabled.
+struct Paint(bool);
+
+impl Paint {
+    #[inline]
+    fn bold(&self, colour: &str, text: &str) -> String {
+        match (self.0, colour) {
+            (false, _) => text.to_string(),
+            (true, "") => format!("\x1b[1m{text}\x1b[0m"),
+            (true, colour) => format!("\x1b[1;{colour}m{text}\x1b[0m"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+    use crate::diagnostics::{Diagnostic, Label, Options, ToDiagnostic};
+    use crate::lexer;
+    use crate::lexer::Tokeniser;
+    use crate::metadata::Metadata;
+    use crate::parser::parse;
+    use crate::symbols::SymbolTable;
+
+    fn render(source: &str, diagnostic: &Diagnostic) -> String {
+        diagnostic.render(source, &Options::default())
+    }
+
+    #[test]
+    fn lexer_error() {
+        let source = "a\nb \\ c\n";
+        let err = Tokeniser::new(source, SymbolTable::default()).find_map(Result::err).unwrap();
+        assert_eq!("\
+error: unexpected character '\\'
+ --> line 2, column 3
+  |
+2 | b \\ c
+  |   ^ unexpected character
+", render(source, &err.to_diagnostic()));
+    }
+
+    #[test]
+    fn parser_error_with_two_labels() {
+        let source = "x [(a]\n";
+        let err = parse(Tokeniser::new(source, SymbolTable::default())).unwrap_err();
+        assert_eq!("\
+error: mismatched closing delimiter ']'
+ --> line 1, column 6
+  |
+1 | x [(a]
+  |    - unclosed delimiter
+  |      ^ mismatched closing delimiter
+  |
+  = help: insert a ')' to close the list opened by '('
+", render(source, &err.to_diagnostic()));
+    }
+
+    #[test]
+    fn multiline_label() {
+        let source = "first\n  second\nthird\n";
+        let diagnostic = Diagnostic::error("spans lines")
+            .with_label(Label::primary(Metadata::bounds(1, 3, 2, 2, 4, 10), "here"));
+        assert_eq!("\
+error: spans lines
+ --> line 1, column 3 to line 2, column 4
+  |
+1 | first
+  |   ^^^
+2 |   second
+  | ^^^^ here
+", render(source, &diagnostic));
+    }
+
+    #[test]
+    fn elided_lines() {
+        let source = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let diagnostic = Diagnostic::error("long")
+            .with_label(Label::primary(Metadata::bounds(2, 1, 2, 10, 2, 20), ""));
+        assert_eq!("\
+error: long
+  --> line 2, column 1 to line 10, column 2
+   |
+ 2 | 2
+   | ^
+ 3 | 3
+   | ^
+...
+ 9 | 9
+   | ^
+10 | 10
+   | ^^
+", render(source, &diagnostic));
+    }
+
+    #[test]
+    fn tabs_preserved_in_indent() {
+        let source = "\tab\n";
+        let diagnostic = Diagnostic::error("tabbed")
+            .with_label(Label::primary(Metadata::bounds(1, 3, 2, 1, 3, 3), "b"));
+        assert_eq!("\
+error: tabbed
+ --> line 1, column 3
+  |
+1 | \tab
+  | \t ^ b
+", render(source, &diagnostic));
+    }
+
+    #[test]
+    fn tab_stops_located_by_offset() {
+        let source = "\ta \\ b\n";
+        let options = lexer::Options {
+            tab_width: NonZeroU32::new(4),
+            ..lexer::Options::default()
+        };
+        let err = Tokeniser::with_options(source, SymbolTable::default(), options).find_map(Result::err).unwrap();
+        assert_eq!("\
+error: unexpected character '\\'
+ --> line 1, column 7
+  |
+1 | \ta \\ b
+  | \t  ^ unexpected character
+", render(source, &err.to_diagnostic()));
+    }
+
+    #[test]
+    fn line_terminators() {
+        let source = "a\r\nb\rc \\\u{2028}d\r\n";
+        let err = Tokeniser::new(source, SymbolTable::default()).find_map(Result::err).unwrap();
+        assert_eq!("\
+error: unexpected character '\\'
+ --> line 3, column 3
+  |
+3 | c \\
+  |   ^ unexpected character
+", render(source, &err.to_diagnostic()));
+    }
+
+    #[test]
+    fn end_before_start_of_line() {
+        let source = "[a\n";
+        let diagnostic = Diagnostic::error("unterminated")
+            .with_label(Label::primary(Metadata::bounds(1, 1, 0, 2, 0, 3), ""));
+        assert_eq!("\
+error: unterminated
+ --> line 1, column 1 to line 2, column 0
+  |
+1 | [a
+  | ^^
+", render(source, &diagnostic));
+    }
+
+    #[test]
+    fn unspecified_metadata_and_notes() {
+        let diagnostic = Diagnostic::error("no location")
+            .with_label(Label::primary(Metadata::unspecified(), "somewhere"))
+            .with_help("try again");
+        assert_eq!("\
+error: no location
+  = note: somewhere
+  = help: try again
+", render("", &diagnostic));
+    }
+
+    #[test]
+    fn colour() {
+        let diagnostic = Diagnostic::error("oops")
+            .with_label(Label::primary(Metadata::bounds(1, 1, 0, 1, 1, 1), "here"));
+        let rendered = diagnostic.render("a\n", &Options { colour: true });
+        assert_eq!("\
+\x1b[1;31merror\x1b[0m\x1b[1m: oops\x1b[0m
+ \x1b[1;34m-->\x1b[0m line 1, column 1
+  \x1b[1;34m|\x1b[0m
+\x1b[1;34m1\x1b[0m \x1b[1;34m|\x1b[0m a
+  \x1b[1;34m|\x1b[0m \x1b[1;31m^ here\x1b[0m
+", rendered);
+    }
+}
@@ -1,14 +1,16 @@
 use std::borrow::Cow;
 use crate::char_buffer::CharBuffer;
-use crate::token::{Ascii, AsciiSlice, ListDelimiter, Token};
-use std::io;
-use std::num::ParseIntError;
+use crate::token::{Ascii, AsciiSlice, BigDecimal, BigInteger, LineEnding, ListDelimiter, Radix, Sign, Token};
+use std::{io, mem};
+use std::num::{IntErrorKind, NonZeroU32, ParseIntError};
 use std::str::FromStr;
 use crate::graphemes::Grapheme;
-use crate::metadata::{Location, Metadata};
-use crate::newline_terminated_bytes::NewlineTerminatedBytes;
+use crate::metadata::{ColumnUnit, Location, Metadata};
+use crate::newline_terminated_bytes::{line_terminator, NewlineTerminatedBytes};
 use crate::symbols::{is_symbol, SymbolString, SymbolTable};
 use crate::token;
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::GraphemeCursor;
 
 #[derive(Debug, thiserror::Error)]
 #[error("codepoint out of range")]
@@ -31,65 +33,620 @@ pub enum Error {
     #[error("invalid codepoint \"{0}\" ({1}) at {2}")]
     InvalidCodepoint(String, Box<dyn std::error::Error>, Location),
 
+    #[error("unpaired surrogate \"{0}\" at {1}")]
+    UnpairedSurrogate(String, Location),
+
     #[error("unparsable integer {0} ({1}) at {2}")]
     UnparsableInteger(String, ParseIntError, Location),
 
     #[error("unparsable decimal {0}.{1} ({2}) at {3}")]
-    UnparsableDecimal(u128, String, ParseIntError, Location),
+    UnparsableDecimal(String, String, ParseIntError, Location),
+
+    #[error("exponent {0} too large at {1}")]
+    ExponentTooLarge(String, Location),
+
+    #[error("unparsable exponent {0} ({1}) at {2}")]
+    UnparsableExponent(String, ParseIntError, Location),
+
+    #[error("unterminated raw literal at {1} (expected {0})")]
+    UnterminatedRawLiteral(String, Location),
 
     #[error("empty character literal at {0}")]
     EmptyCharacterLiteral(Location),
+
+    #[error("unterminated comment at {0}")]
+    UnterminatedComment(Location),
+
+    #[error("invalid comment delimiter \"{0}\" ({1})")]
+    InvalidCommentDelimiter(String, InvalidDelimiter),
 }
 
+/// Why a delimiter in the [`Comments`] options was rejected.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidDelimiter {
+    #[error("empty")]
+    Empty,
+
+    #[error("not ASCII")]
+    NonAscii,
+
+    #[error("does not begin with a symbol character")]
+    NotSymbol,
+
+    #[error("hides symbol {0}")]
+    HidesSymbol(String),
+}
+
+/// The comment syntax recognised by the [`Tokeniser`]. Comment openers must begin with a symbol
+/// character (see [`is_symbol`]), and may not begin a symbol in the [`SymbolTable`], which would
+/// otherwise never be lexed. All sequences must be nonempty ASCII. A tokeniser given invalid
+/// delimiters yields an [`Error::InvalidCommentDelimiter`] and nothing else.
+#[derive(Debug, Clone)]
+pub struct Comments {
+    /// Opens a comment that runs to the end of the line.
+    pub line: Option<Cow<'static, str>>,
+
+    /// Opens and closes a (nestable) block comment.
+    pub block: Option<(Cow<'static, str>, Cow<'static, str>)>,
+
+    /// Whether comments are emitted as [`Token::Comment`] or silently discarded.
+    pub emit: bool,
+}
+
+impl Comments {
+    pub fn none() -> Self {
+        Self {
+            line: None,
+            block: None,
+            emit: false,
+        }
+    }
+
+    /// `//` line comments and `/* */` block comments, as in C.
+    pub fn c_style() -> Self {
+        Self {
+            line: Some("//".into()),
+            block: Some(("/*".into(), "*/".into())),
+            emit: false,
+        }
+    }
+
+    /// Checks the delimiters against the rules above.
+    fn validate(&self, symbol_table: &SymbolTable) -> Result<(), Box<Error>> {
+        let invalid = |delimiter: &str, reason| Err(Error::InvalidCommentDelimiter(delimiter.to_string(), reason).into());
+        let openers = self.line.iter().chain(self.block.iter().map(|(open, _)| open));
+        let closers = self.block.iter().map(|(_, close)| close);
+        for delimiter in openers.clone().chain(closers) {
+            if delimiter.is_empty() {
+                return invalid(delimiter, InvalidDelimiter::Empty)
+            }
+            if !delimiter.is_ascii() {
+                return invalid(delimiter, InvalidDelimiter::NonAscii)
+            }
+        }
+        for opener in openers {
+            if !is_symbol(opener.as_bytes()[0]) {
+                return invalid(opener, InvalidDelimiter::NotSymbol)
+            }
+            if let Some(symbol) = symbol_table.first_with_prefix(opener.as_bytes()) {
+                let symbol = String::from_utf8_lossy(&symbol.0).into_owned();
+                return invalid(opener, InvalidDelimiter::HidesSymbol(symbol))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for Comments {
+    /// No comments, so that every symbol in the [`SymbolTable`] is lexed as such.
+    #[inline]
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// An escape sequence in a text, character or byte literal.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Escape {
+    /// `\\`
+    Backslash,
+    /// `\"`
+    Quote,
+    /// `\'`
+    Apostrophe,
+    /// `\/`
+    Slash,
+    /// `\0`
+    Nul,
+    /// `\a`
+    Bell,
+    /// `\b`
+    Backspace,
+    /// `\t`
+    Tab,
+    /// `\n`
+    Newline,
+    /// `\v`
+    VerticalTab,
+    /// `\f`
+    FormFeed,
+    /// `\r`
+    CarriageReturn,
+    /// `\e`
+    Esc,
+    /// `\$`, for a literal `$` directly ahead of a `{` in an interpolated text
+    Dollar,
+    /// `\xNN`
+    Hex,
+    /// `\uNNNN`, where surrogate pairs are combined
+    Unicode,
+    /// `\u{N...}`
+    UnicodeBraced,
+    /// A backslash at the end of a line in a text or character literal, which swallows the line
+    /// break and the leading blanks on the next line.
+    LineContinuation,
+}
+
+/// The set of escape sequences recognised by the [`Tokeniser`]. A disabled escape is reported as
+/// an [`Error::UnknownEscapeSequence`]. Byte literals never recognise the Unicode escapes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Escapes(u32);
+
+impl Escapes {
+    pub const fn none() -> Self {
+        Self(0)
+    }
+
+    /// Every escape sequence, as in Mercury.
+    pub const fn mercury() -> Self {
+        Self(u32::MAX)
+    }
+
+    /// The escape sequences of strict JSON.
+    pub const fn json() -> Self {
+        Self::none()
+            .with(Escape::Backslash)
+            .with(Escape::Quote)
+            .with(Escape::Slash)
+            .with(Escape::Backspace)
+            .with(Escape::FormFeed)
+            .with(Escape::Newline)
+            .with(Escape::CarriageReturn)
+            .with(Escape::Tab)
+            .with(Escape::Unicode)
+    }
+
+    pub const fn with(self, escape: Escape) -> Self {
+        Self(self.0 | 1 << escape as u32)
+    }
+
+    pub const fn without(self, escape: Escape) -> Self {
+        Self(self.0 & !(1 << escape as u32))
+    }
+
+    #[inline(always)]
+    pub const fn allows(self, escape: Escape) -> bool {
+        self.0 & 1 << escape as u32 != 0
+    }
+}
+
+impl Default for Escapes {
+    #[inline]
+    fn default() -> Self {
+        Self::mercury()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// The comment syntax. By default, no comments are recognised, so that a grammar using `//`
+    /// or `/*` as symbols lexes as it did before comments were supported. Use
+    /// [`Comments::c_style`] to recognise (and discard) C-style comments.
+    pub comments: Comments,
+
+    pub escapes: Escapes,
+
+    /// Whether `<` and `>` may delimit lists. When enabled, a `<` directly attached to a preceding
+    /// identifier (as in `Vec<T>`) opens an angle list, unless it is followed by a `=`, a `<`,
+    /// whitespace or a digit (as in `x<=5`, `x<<2` or `x<5`), or no matching `>` follows on the
+    /// same line before the enclosing list closes (as in `a<b` or `(a<b)`). An angle list thus
+    /// cannot span lines. A `>` closes an angle list if the innermost open list is an angle list,
+    /// unless it continues a symbol in the [`SymbolTable`] (as the `>` in `->`). All other
+    /// occurrences are lexed as symbols, though an unspaced comparison followed by a `>` on the
+    /// same line, as in `x<y>z`, is still taken for an angle list.
+    pub angle_lists: bool,
+
+    /// Whether a `-` or `+` directly attached to a number is folded into a [`Token::Signed`],
+    /// provided the previous token is not an operand (a value, identifier or closing delimiter),
+    /// as in `[-1]` or `2 * -3`. Thus, `1 -1` is still lexed as a subtraction.
+    pub signed_numbers: bool,
+
+    /// Whether a text literal containing an embedded expression, as in `"Hello ${name}!"`, is
+    /// lexed as an interpolated text. Such a literal is emitted as a [`Token::InterpolationBegin`],
+    /// followed by its [`Token::TextSegment`]s and its embedded expressions (each delimited by a
+    /// [`Token::EmbedBegin`] and a [`Token::EmbedEnd`]), and ends with a
+    /// [`Token::InterpolationEnd`]. Other text literals are lexed as usual. An embedded expression
+    /// may not span lines.
+    pub interpolation: bool,
+
+    /// The unit in which columns are counted. By default, a column is a Unicode scalar value.
+    pub column_unit: ColumnUnit,
+
+    /// The distance between tab stops, in columns. A tab advances the column to the next tab stop,
+    /// as in an editor displaying tabs at this width. By default, a tab counts as a single column.
+    /// Either way, the offset of a [`Location`] gives its raw position.
+    pub tab_width: Option<NonZeroU32>,
+
+    /// Whether tokenisation carries on after an error. The remainder of the malformed token is
+    /// skipped: up to the closing quote of a literal, the end of the line within an interpolated
+    /// text, or the next whitespace, delimiter or symbol otherwise. By default, the first error
+    /// ends tokenisation.
+    pub recover: bool,
+}
+
+#[derive(Debug, Clone)]
 enum Mode {
     Whitespace,
     Text,
+    /// Within a segment of an interpolated text.
+    Segment,
+    /// Holds the number of hashes in the fence and the offset of the content.
+    RawText(usize, usize),
+    /// Holds the offset of the content.
+    TextBlock(usize),
     Character,
+    /// Holds the offset of the content.
+    ByteText(usize),
+    ByteCharacter(Option<u8>),
     Integer,
-    Decimal(u128),
-    Ident
+    RadixInteger(Radix),
+    /// Holds the position of the decimal point in the token.
+    Decimal(usize),
+    Exponent(Significand),
+    Ident,
+    LineComment(usize),
+    BlockComment(usize, u32),
+    /// Skipping the remainder of a malformed token.
+    Recovery(Resync),
+}
+
+/// The significand of a number in scientific notation.
+#[derive(Debug, Clone)]
+enum Significand {
+    Decimal(token::Decimal),
+    /// The whole and fractional digits of a significand too large for a [`token::Decimal`].
+    Big(String, String),
+}
+
+/// The largest magnitude of the exponent of a number in scientific notation, whatever the size
+/// of its significand. It bounds the number of zeros padding the digits of a big significand
+/// expanded into a [`BigDecimal`].
+pub const MAX_EXPONENT: u32 = 1 << 16;
+
+/// Where tokenisation resynchronises after an error.
+#[derive(Debug, Clone, Copy)]
+enum Resync {
+    /// Before the next whitespace, delimiter or symbol.
+    Token,
+    /// After the given closing quote.
+    Quote(u8),
+    /// After the byte following a backslash, then as for [`Resync::Quote`].
+    Escaped(u8),
+    /// Before the end of the line.
+    Line,
+}
+
+/// The state of a [`Tokeniser`] between two tokens, from which tokenisation may be resumed over
+/// a subsequent slice of the input.
+#[derive(Debug, Clone)]
+pub(crate) struct Checkpoint {
+    /// The offset of the first byte that is yet to be tokenised.
+    pub offset: usize,
+    /// The same offset, measured from the start of the input.
+    base: usize,
+    start: Location,
+    location: Location,
+    open_lists: Vec<ListDelimiter>,
+    ident_attached: bool,
+    operand_last: bool,
+    embeds: Vec<usize>,
+    mode: Mode,
 }
 
+impl Checkpoint {
+    #[inline]
+    pub fn before_start() -> Self {
+        Self {
+            offset: 0,
+            base: 0,
+            start: Location::before_start(),
+            location: Location::before_start(),
+            open_lists: vec![],
+            ident_attached: false,
+            operand_last: false,
+            embeds: vec![],
+            mode: Mode::Whitespace,
+        }
+    }
+}
+
+/// Opens and closes a text block, which may span multiple lines. Its content is taken verbatim,
+/// without interpreting escape sequences, then dedented (see [`dedent`]).
+const TEXT_BLOCK_FENCE: &str = "\"\"\"";
+
 pub struct Tokeniser<'a, 's> {
     symbol_table: SymbolTable<'s>,
+    options: Options,
     bytes: &'a [u8],
     byte_indexes: NewlineTerminatedBytes<'a>,
+    /// The offset of the first of the bytes within the input.
+    base: usize,
     token: CharBuffer,
     mode: Mode,
     start: Location,
+    /// The location of the last character read. Its offset is not maintained; see
+    /// [`here`](Self::here).
     location: Location,
+    /// The column before the last byte read was counted, to which it reverts if the byte is
+    /// stashed.
+    previous_column: u32,
     stashed_byte: Option<(usize, u8)>,
+    open_lists: Vec<ListDelimiter>,
+    ident_attached: bool,
+    operand_last: bool,
+    /// For each embedded expression in progress, innermost last, the number of braces opened
+    /// within it and not yet closed.
+    embeds: Vec<usize>,
+    sign: Option<(Sign, Location)>,
+    byte_copy: Option<Vec<u8>>,
+    partial: bool,
+    /// An error to be yielded before anything else, as for invalid options.
+    pending_error: Option<Box<Error>>,
     error: bool
 }
 
 impl<'a, 's> Tokeniser<'a, 's> {
     #[inline]
     pub fn new(str: &'a str, symbol_table: SymbolTable<'s>) -> Self {
+        Self::with_options(str, symbol_table, Options::default())
+    }
+
+    #[inline]
+    pub fn with_options(str: &'a str, symbol_table: SymbolTable<'s>, options: Options) -> Self {
+        let pending_error = options.comments.validate(&symbol_table).err();
         Self {
             symbol_table,
+            options,
             bytes: str.as_bytes(),
             byte_indexes:  NewlineTerminatedBytes::new(str.bytes()),
+            base: 0,
             token: CharBuffer::default(),
             mode: Mode::Whitespace,
             start: Location::before_start(),
             location: Location::before_start(),
+            previous_column: 0,
             stashed_byte: None,
+            open_lists: vec![],
+            ident_attached: false,
+            operand_last: false,
+            embeds: vec![],
+            sign: None,
+            byte_copy: None,
+            partial: false,
+            pending_error,
             error: false,
         }
     }
 
+    /// Resumes tokenisation from a checkpoint, where `str` begins at the checkpoint's offset.
+    /// A partial tokeniser does not treat the end of `str` as the end of the input, leaving the
+    /// token in progress unfinished. For this to work, a partial `str` must end in whitespace.
+    #[inline]
+    pub(crate) fn resume(str: &'a str, symbol_table: SymbolTable<'s>, options: Options, checkpoint: Checkpoint, partial: bool) -> Self {
+        let mut tokeniser = Self::with_options(str, symbol_table, options);
+        if partial {
+            tokeniser.byte_indexes = NewlineTerminatedBytes::unterminated(str.bytes());
+        }
+        tokeniser.base = checkpoint.base;
+        tokeniser.start = checkpoint.start;
+        tokeniser.location = checkpoint.location;
+        tokeniser.open_lists = checkpoint.open_lists;
+        tokeniser.ident_attached = checkpoint.ident_attached;
+        tokeniser.operand_last = checkpoint.operand_last;
+        tokeniser.embeds = checkpoint.embeds;
+        tokeniser.mode = checkpoint.mode;
+        tokeniser.partial = partial;
+        tokeniser
+    }
+
+    /// Captures the tokeniser's state. Only meaningful immediately after a token is emitted, or
+    /// while [`resumable`](Self::resumable).
+    #[inline]
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        let offset = self.consumed();
+        let mode = match self.mode {
+            // a discarded comment may have opened before the checkpoint; its offset is never read
+            Mode::LineComment(_) => Mode::LineComment(0),
+            Mode::BlockComment(_, depth) => Mode::BlockComment(0, depth),
+            ref mode => mode.clone()
+        };
+        Checkpoint {
+            offset,
+            base: self.base + offset,
+            start: self.start.clone(),
+            location: self.location.clone(),
+            open_lists: self.open_lists.clone(),
+            ident_attached: self.ident_attached,
+            operand_last: self.operand_last,
+            embeds: self.embeds.clone(),
+            mode,
+        }
+    }
+
+    /// Whether the tokeniser holds nothing of the input consumed so far, other than in its
+    /// [`Checkpoint`], as between tokens or within a discarded comment. Tokenisation may then be
+    /// resumed from a checkpoint taken now.
+    #[inline]
+    pub(crate) fn resumable(&self) -> bool {
+        self.token.is_empty() && self.sign.is_none() && self.byte_copy.is_none() && match self.mode {
+            Mode::Whitespace | Mode::Recovery(_) => true,
+            Mode::LineComment(_) | Mode::BlockComment(_, _) => !self.options.comments.emit,
+            _ => false
+        }
+    }
+
+    /// The first line terminator read, which a printer may use to write the input back the same
+    /// way. `None` if no line has been terminated yet (a newline appended to the last line does
+    /// not count).
+    #[inline]
+    pub fn line_ending(&self) -> Option<LineEnding> {
+        self.byte_indexes.line_ending()
+    }
+
+    /// The offset of the first byte that is yet to be consumed, less the base.
+    #[inline(always)]
+    fn consumed(&self) -> usize {
+        match self.stashed_byte {
+            None => self.byte_indexes.offset(),
+            Some((index, _)) => index
+        }
+    }
+
+    /// The current location, with the offset just past the last byte consumed.
+    #[inline(always)]
+    fn here(&self) -> Location {
+        Location { offset: self.base + self.consumed(), ..self.location.clone() }
+    }
+
+    /// The current location, with the offset of the character there, which starts at the given
+    /// index.
+    #[inline(always)]
+    fn here_at(&self, index: usize) -> Location {
+        Location { offset: self.base + index, ..self.location.clone() }
+    }
+
+    /// Counts the column of the byte just read, advancing to the next tab stop for a tab.
+    #[inline(always)]
+    fn count_column(&mut self, byte: u8) {
+        self.previous_column = self.location.column;
+        self.location.column += 1;
+        if byte == b'\t' && let Some(tab_width) = self.options.tab_width {
+            self.location.column = self.location.column.div_ceil(tab_width.get()) * tab_width.get();
+        }
+    }
+
+    /// Uncounts the column of the byte just read, which has been stashed to be read again.
+    #[inline(always)]
+    fn uncount_column(&mut self) {
+        self.location.column = self.previous_column;
+    }
+
     #[inline(always)]
     fn next_byte(&mut self) -> Option<(usize, u8)> {
         self.stashed_byte.take().or_else(|| self.byte_indexes.next())
     }
 
+    /// Reads the remainder of a multibyte character, the first byte of which has already been
+    /// read and counted as a column, bringing the column up to date in the configured unit.
+    #[inline]
+    fn read_grapheme(&mut self, byte: u8) -> Grapheme {
+        #[cfg(feature = "graphemes")]
+        let index = self.byte_indexes.offset() - 1;
+        let grapheme = read_grapheme(byte, &mut self.byte_indexes);
+        match self.options.column_unit {
+            ColumnUnit::Chars => {}
+            ColumnUnit::Bytes => self.location.column += grapheme.len_utf8() as u32 - 1,
+            ColumnUnit::Utf16 => self.location.column += char::from(grapheme).len_utf16() as u32 - 1,
+            #[cfg(feature = "graphemes")]
+            ColumnUnit::Graphemes => {
+                // a character that extends the preceding cluster does not count; lacking the
+                // context to tell, as at the start of a partial input, it is assumed not to
+                let str = unsafe { str::from_utf8_unchecked(self.bytes) };
+                if !GraphemeCursor::new(index, str.len(), true).is_boundary(str, 0).unwrap_or(true) {
+                    self.location.column -= 1;
+                }
+            }
+        }
+        grapheme
+    }
+
+    #[inline(always)]
+    fn starts_with(&self, index: usize, sequence: &str) -> bool {
+        self.bytes[index..].starts_with(sequence.as_bytes())
+    }
+
+    /// Consumes the remainder of a sequence, the first byte of which has already been read.
+    #[inline]
+    fn skip_sequence(&mut self, sequence_len: usize) {
+        for _ in 1..sequence_len {
+            self.next_byte();
+            self.location.column += 1;
+        }
+    }
+
+    /// Returns the comment mode and the length of its opening sequence if a comment starts at
+    /// the given index. Where both openers match, the longer one wins.
+    #[inline]
+    fn comment_opener(&self, index: usize) -> Option<(Mode, usize)> {
+        let comments = &self.options.comments;
+        let line = comments.line.as_ref()
+            .filter(|line| self.starts_with(index, line))
+            .map(|line| (Mode::LineComment(index), line.len()));
+        let block = comments.block.as_ref()
+            .filter(|(open, _)| self.starts_with(index, open))
+            .map(|(open, _)| (Mode::BlockComment(index, 1), open.len()));
+        match (line, block) {
+            (Some(line), Some(block)) => {
+                if block.1 > line.1 { Some(block) } else { Some(line) }
+            }
+            (line, block) => line.or(block)
+        }
+    }
+
+    #[inline]
+    fn make_comment(&mut self, offset: usize, end: usize) -> Option<Fragment<'a>> {
+        self.mode = Mode::Whitespace;
+        if self.options.comments.emit {
+            let str = unsafe { str::from_utf8_unchecked(&self.bytes[offset..end]) };
+            self.frame_token(Token::Comment(Cow::Borrowed(str)))
+        } else {
+            self.skip_token();
+            None
+        }
+    }
+
+    /// Whether a sign at the given index should be folded into the number that follows it.
+    #[inline]
+    fn sign_attached(&self, index: usize) -> bool {
+        if !self.options.signed_numbers || self.operand_last {
+            return false
+        }
+        matches!(self.bytes.get(index + 1..), Some([b'0'..=b'9', ..] | [b'.', b'0'..=b'9', ..]))
+    }
+
+    /// Returns the number of hashes in the fence if a raw text literal starts at the given index.
+    #[inline]
+    fn raw_text_opener(&self, index: usize) -> Option<usize> {
+        let hashes = self.bytes[index + 1..].iter().take_while(|&&byte| byte == b'#').count();
+        match self.bytes.get(index + 1 + hashes) {
+            Some(b'"') => Some(hashes),
+            _ => None
+        }
+    }
+
+    #[inline]
+    fn raw_text_closer(&self, index: usize, hashes: usize) -> bool {
+        let fence = &self.bytes[index + 1..];
+        fence.len() >= hashes && fence[..hashes].iter().all(|&byte| byte == b'#')
+    }
+
     #[inline(always)]
     fn make_symbol(&mut self) -> Token<'a> {
         //println!("making symbol with string \"{}\"", self.token.string(self.bytes));
         let token = if self.token.len() == 1 {
             Token::Symbol(Ascii(self.token.first_byte(self.bytes)))
         } else {
-            Token::ExtendedSymbol(AsciiSlice(self.token.make_byte_slice(self.bytes)))
+            Token::ExtendedSymbol(AsciiSlice(Cow::Borrowed(self.token.make_byte_slice(self.bytes))))
         };
         self.token.clear();
         token
@@ -109,10 +666,9 @@ impl<'a, 's> Tokeniser<'a, 's> {
                     return Some(self.make_symbol())
                 }
             } else if self.token.len() == 1 && self.token.first_byte(self.bytes) == b'.' && byte.is_ascii_digit() {
-                self.token.clear();
                 self.stashed_byte = Some((index, byte)); // don't consume the char
                 self.mode = Mode::Decimal(0);
-                self.start = self.location.clone();
+                self.start = self.here_at(self.token.offset());
                 return None
             } else {
                 self.stashed_byte = Some((index, byte)); // don't consume the char
@@ -123,7 +679,9 @@ impl<'a, 's> Tokeniser<'a, 's> {
     }
 
     #[inline]
-    fn parse_escape(&mut self) -> Result<char, Box<Error>> {
+    /// Parses an escape sequence into the character it denotes, or `None` for a line
+    /// continuation.
+    fn parse_escape(&mut self) -> Result<Option<char>, Box<Error>> {
         enum EscapeState {
             Single,
             Hex,
@@ -133,78 +691,202 @@ impl<'a, 's> Tokeniser<'a, 's> {
 
         let mut buf = String::new();
         let mut state = EscapeState::Single;
-        while let Some((_, byte)) = self.next_byte() {
-            self.location.column += 1;
+        while let Some((index, byte)) = self.next_byte() {
+            self.count_column(byte);
             if byte == b'\n' {
+                if let EscapeState::Single = state && self.options.escapes.allows(Escape::LineContinuation) {
+                    self.continue_line();
+                    return Ok(None)
+                }
                 self.error = true;
                 let str = unsafe { String::from_utf8_unchecked(vec![byte]) };
-                return Err(Error::UnknownEscapeSequence(str, self.location.clone()).into())
+                return Err(Error::UnknownEscapeSequence(str, self.here()).into())
             } else if byte < 0x80 {
+                let escapes = self.options.escapes;
                 match state {
                     EscapeState::Single => {
                         match byte {
-                            b'\\' | b'"' | b'\'' => {
-                                return Ok(byte as char)
-                            }
-                            b'n' => {
-                                return Ok('\n')
-                            }
-                            b'r' => {
-                                return Ok('\r')
-                            }
-                            b't' => {
-                                return Ok('\t')
+                            _ if let Some((escape, value)) = simple_escape(byte) && escapes.allows(escape) => {
+                                return Ok(Some(value as char))
                             }
-                            b'0' => {
-                                return Ok('\0')
-                            }
-                            b'x' => {
+                            b'x' if escapes.allows(Escape::Hex) => {
                                 state = EscapeState::Hex
                             }
-                            b'u' => {
+                            b'u' if escapes.allows(Escape::Unicode) || escapes.allows(Escape::UnicodeBraced) => {
                                 state = EscapeState::UnicodeFixed
                             }
                             _ => {
                                 self.error = true;
                                 let str = unsafe { String::from_utf8_unchecked(vec![byte]) };
-                                return Err(Error::UnknownEscapeSequence(str, self.location.clone()).into())
+                                return Err(Error::UnknownEscapeSequence(str, self.here()).into())
                             }
                         }
                     }
                     EscapeState::Hex => {
                         buf.push(byte as char);
-                        if buf.len() == 2 {
-                            return self.make_unicode(&buf)
+                        if buf.len() == 2 || is_blank(byte) {
+                            return self.make_unicode(&buf).map(Some)
                         }
                     }
                     EscapeState::UnicodeFixed => {
-                        if buf.is_empty() && byte == b'{' {
+                        if buf.is_empty() && !escapes.allows(if byte == b'{' { Escape::UnicodeBraced } else { Escape::Unicode }) {
+                            self.error = true;
+                            return Err(Error::UnknownEscapeSequence(format!("u{}", byte as char), self.here()).into())
+                        } else if buf.is_empty() && byte == b'{' {
                             state = EscapeState::UnicodeVariable;
                         } else {
                             buf.push(byte as char);
                             if buf.len() == 4 {
-                                return self.make_unicode(&buf)
+                                return self.make_utf16(index, &buf).map(Some)
+                            } else if is_blank(byte) {
+                                return self.make_unicode(&buf).map(Some)
                             }
                         }
                     }
                     EscapeState::UnicodeVariable => {
                         if byte == b'}' {
-                            return self.make_unicode(&buf)
+                            return self.make_unicode(&buf).map(Some)
                         } else {
                             buf.push(byte as char);
+                            if is_blank(byte) {
+                                return self.make_unicode(&buf).map(Some)
+                            }
                         }
                     }
                 }
             } else {
                 self.error = true;
-                let grapheme = read_grapheme(byte, &mut self.byte_indexes);
+                let grapheme = self.read_grapheme(byte);
                 buf.push(char::from(grapheme));
-                return Err(Error::UnknownEscapeSequence(buf, self.location.clone()).into())
+                return Err(Error::UnknownEscapeSequence(buf, self.here()).into())
             }
         }
         unreachable!() // since '\n' is guaranteed to terminate the stream (handled in the loop above)
     }
 
+    /// Whether the text literal opening at the given index embeds an expression, scanning ahead
+    /// to its closing quote.
+    #[inline]
+    fn interpolated(&self, index: usize) -> bool {
+        let mut index = index + 1;
+        while let Some(&byte) = self.bytes.get(index) {
+            match byte {
+                b'\\' => index += 1,
+                b'"' => return false,
+                b'\n' | b'\r' | 0xC2 | 0xE2 if line_terminator(&self.bytes[index..]).is_some() => return false,
+                b'$' if self.bytes.get(index + 1) == Some(&b'{') => return true,
+                _ => {}
+            }
+            index += 1;
+        }
+        false
+    }
+
+    /// Swallows the line break ending a line continuation and the blanks leading the next line.
+    #[inline]
+    fn continue_line(&mut self) {
+        self.location.line += 1;
+        self.location.column = 0;
+        while let Some((index, byte)) = self.next_byte() {
+            if !is_blank(byte) {
+                self.stashed_byte = Some((index, byte));
+                break
+            }
+            self.count_column(byte);
+        }
+    }
+
+    /// Parses an escape sequence in a byte literal, in which `\x` may denote any byte value.
+    #[inline]
+    fn parse_byte_escape(&mut self) -> Result<u8, Box<Error>> {
+        let Some((_, byte)) = self.next_byte() else {
+            unreachable!() // since '\n' is guaranteed to terminate the stream
+        };
+        self.count_column(byte);
+        let escapes = self.options.escapes;
+        match byte {
+            _ if let Some((escape, value)) = simple_escape(byte) && escapes.allows(escape) => Ok(value),
+            b'x' if escapes.allows(Escape::Hex) => {
+                let mut digits = String::from("x");
+                while digits.len() < 3 {
+                    let Some((_, byte)) = self.next_byte() else {
+                        unreachable!() // since '\n' is guaranteed to terminate the stream
+                    };
+                    self.count_column(byte);
+                    if byte >= 0x80 {
+                        digits.push(char::from(self.read_grapheme(byte)));
+                        break
+                    }
+                    digits.push(byte as char);
+                    if !byte.is_ascii_hexdigit() {
+                        break
+                    }
+                }
+                match digits.as_bytes() {
+                    [_, high, low] if high.is_ascii_hexdigit() && low.is_ascii_hexdigit() => {
+                        Ok(u8::from_str_radix(&digits[1..], 16).unwrap())
+                    }
+                    _ => {
+                        self.error = true;
+                        Err(Error::UnknownEscapeSequence(digits, self.here()).into())
+                    }
+                }
+            }
+            _ => {
+                self.error = true;
+                let str = if byte < 0x80 {
+                    (byte as char).to_string()
+                } else {
+                    char::from(self.read_grapheme(byte)).to_string()
+                };
+                Err(Error::UnknownEscapeSequence(str, self.here()).into())
+            }
+        }
+    }
+
+    /// Reports a non-ASCII or surplus character in a byte literal.
+    #[inline]
+    fn unexpected_character(&mut self, byte: u8) -> Fragment<'a> {
+        self.error = true;
+        let char = if byte < 0x80 {
+            byte as char
+        } else {
+            char::from(self.read_grapheme(byte))
+        };
+        Err(Error::UnexpectedCharacter(char, self.here()).into())
+    }
+
+    /// Makes a character from a fixed-width escape ending at the given index. As in JSON, a high
+    /// surrogate must be immediately followed by a low surrogate escape, the two combining into
+    /// one character.
+    #[inline]
+    fn make_utf16(&mut self, index: usize, buf: &str) -> Result<char, Box<Error>> {
+        match u32::from_str_radix(buf, 16) {
+            Ok(high @ 0xD800..=0xDBFF) => {
+                let low = self.bytes.get(index + 1..index + 7)
+                    .and_then(|escape| escape.strip_prefix(b"\\u"))
+                    .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+                    .map(|digits| u32::from_str_radix(unsafe { str::from_utf8_unchecked(digits) }, 16).unwrap())
+                    .filter(|low| (0xDC00..=0xDFFF).contains(low));
+                match low {
+                    Some(low) => {
+                        self.skip_sequence(7); // the last digit of the high surrogate and the low surrogate escape
+                        Ok(char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).unwrap())
+                    }
+                    None => {
+                        self.error = true;
+                        Err(Error::UnpairedSurrogate(buf.to_string(), self.here()).into())
+                    }
+                }
+            }
+            Ok(0xDC00..=0xDFFF) => {
+                self.error = true;
+                Err(Error::UnpairedSurrogate(buf.to_string(), self.here()).into())
+            }
+            _ => self.make_unicode(buf)
+        }
+    }
+
     #[inline]
     fn make_unicode(&mut self, buf: &str) -> Result<char, Box<Error>> {
         match u32::from_str_radix(buf, 16) {
@@ -212,14 +894,14 @@ impl<'a, 's> Tokeniser<'a, 's> {
                 match char::from_u32(hex) {
                     None => {
                         self.error = true;
-                        Err(Error::InvalidCodepoint(buf.to_string(), Box::new(CodepointOutOfRange), self.location.clone()).into())
+                        Err(Error::InvalidCodepoint(buf.to_string(), Box::new(CodepointOutOfRange), self.here()).into())
                     }
                     Some(char) => Ok(char)
                 }
             }
             Err(err) => {
                 self.error = true;
-                Err(Error::InvalidCodepoint(buf.to_string(), Box::new(err), self.location.clone()).into())
+                Err(Error::InvalidCodepoint(buf.to_string(), Box::new(err), self.here()).into())
             }
         }
     }
@@ -227,36 +909,118 @@ impl<'a, 's> Tokeniser<'a, 's> {
     #[inline]
     fn make_integer(&mut self) -> Option<Fragment<'a>> {
         let str = self.token.as_str(self.bytes);
-        match u128::from_str(str) {
+        match parse_digits(str, 10) {
             Ok(whole) => {
-                let token = Token::Integer(whole);
+                let token = match whole {
+                    Some(whole) => Token::Integer(whole),
+                    None => Token::BigInteger(BigInteger(self.token.string(self.bytes), None))
+                };
                 self.token.clear();
                 self.mode = Mode::Whitespace;
-                self.location.column -= 1;
+                self.uncount_column();
                 self.frame_token(token)
             }
             Err(err) => {
                 self.error = true;
-                Some(Err(Error::UnparsableInteger(str.to_string(), err, self.location.clone()).into()))
+                Some(Err(Error::UnparsableInteger(str.to_string(), err, self.here()).into()))
             }
         }
     }
 
     #[inline]
-    fn make_decimal(&mut self, whole: u128) -> Option<Fragment<'a>> {
+    fn make_radix_integer(&mut self, radix: Radix) -> Option<Fragment<'a>> {
         let str = self.token.as_str(self.bytes);
-        match u128::from_str(str) {
-            Ok(fractional) => {
-                let scale = self.token.len().try_into().expect("fractional part is too long");
-                let token = Token::Decimal(token::Decimal(whole, fractional, scale));
+        match parse_digits(str, radix.base()) {
+            Ok(value) => {
+                let token = match value {
+                    Some(value) => Token::RadixInteger(value, radix),
+                    None => Token::BigInteger(BigInteger(self.token.string(self.bytes), Some(radix)))
+                };
                 self.token.clear();
                 self.mode = Mode::Whitespace;
-                self.location.column -= 1;
+                self.uncount_column();
                 self.frame_token(token)
             }
             Err(err) => {
                 self.error = true;
-                Some(Err(Error::UnparsableDecimal(whole, str.to_string(), err, self.location.clone()).into()))
+                Some(Err(Error::UnparsableInteger(format!("{}{str}", radix.prefix()), err, self.here()).into()))
+            }
+        }
+    }
+
+    /// Parses a decimal from a token containing the decimal point at the given position, falling
+    /// back to a [`BigDecimal`] if either part is too large. The token is left intact.
+    #[inline]
+    fn parse_decimal(&mut self, point: usize) -> Result<Token<'a>, Box<Error>> {
+        let str = self.token.as_str(self.bytes);
+        let (whole_str, fractional_str) = (&str[..point], &str[point + 1..]);
+        let whole = if whole_str.is_empty() { Ok(Some(0)) } else { parse_digits(whole_str, 10) };
+        match (whole, parse_digits(fractional_str, 10)) {
+            (Ok(Some(whole)), Ok(Some(fractional))) if fractional_str.len() <= u8::MAX as usize => {
+                let scale = fractional_str.len() as u8;
+                Ok(Token::Decimal(token::Decimal(whole, fractional, scale)))
+            }
+            (Ok(_), Ok(_)) => {
+                let (whole, fractional) = match self.token.string(self.bytes) {
+                    Cow::Borrowed(str) => (Cow::Borrowed(&str[..point]), Cow::Borrowed(&str[point + 1..])),
+                    Cow::Owned(string) => (Cow::Owned(string[..point].to_string()), Cow::Owned(string[point + 1..].to_string()))
+                };
+                let whole = if whole.is_empty() { Cow::Borrowed("0") } else { whole };
+                Ok(Token::BigDecimal(BigDecimal(whole, fractional)))
+            }
+            (Err(err), _) => {
+                self.error = true;
+                Err(Error::UnparsableInteger(whole_str.to_string(), err, self.here()).into())
+            }
+            (_, Err(err)) => {
+                self.error = true;
+                let whole_str = if whole_str.is_empty() { "0" } else { whole_str };
+                Err(Error::UnparsableDecimal(whole_str.to_string(), fractional_str.to_string(), err, self.here()).into())
+            }
+        }
+    }
+
+    #[inline]
+    fn make_decimal(&mut self, point: usize) -> Option<Fragment<'a>> {
+        match self.parse_decimal(point) {
+            Ok(token) => {
+                self.token.clear();
+                self.mode = Mode::Whitespace;
+                self.uncount_column();
+                self.frame_token(token)
+            }
+            Err(err) => Some(Err(err))
+        }
+    }
+
+    /// Makes a number in scientific notation, provided the exponent is within [`MAX_EXPONENT`]. A
+    /// big significand is expanded by the exponent into a decimal (a [`BigDecimal`], unless it
+    /// now fits in a [`token::Decimal`]).
+    #[inline]
+    fn make_scientific(&mut self, significand: Significand) -> Option<Fragment<'a>> {
+        let str = self.token.as_str(self.bytes);
+        match i32::from_str(str) {
+            Ok(exponent) if exponent.unsigned_abs() <= MAX_EXPONENT => {
+                let token = match significand {
+                    Significand::Decimal(significand) => Token::Scientific(token::Scientific(significand, exponent)),
+                    Significand::Big(whole, fractional) => expand_scientific(&whole, &fractional, exponent)
+                };
+                self.token.clear();
+                self.mode = Mode::Whitespace;
+                self.uncount_column();
+                self.frame_token(token)
+            }
+            Ok(_) => {
+                self.error = true;
+                Some(Err(Error::ExponentTooLarge(str.to_string(), self.here()).into()))
+            }
+            Err(err) if matches!(err.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) => {
+                self.error = true;
+                Some(Err(Error::ExponentTooLarge(str.to_string(), self.here()).into()))
+            }
+            Err(err) => {
+                self.error = true;
+                Some(Err(Error::UnparsableExponent(str.to_string(), err, self.here()).into()))
             }
         }
     }
@@ -277,17 +1041,156 @@ impl<'a, 's> Tokeniser<'a, 's> {
         };
         self.token.clear();
         self.mode = Mode::Whitespace;
-        self.location.column -= 1;
+        self.uncount_column();
+        self.ident_attached = true;
         self.frame_token(token)
     }
 
+    /// Emits the pending segment of an interpolated text, stashing the byte that ended it.
+    #[inline]
+    fn make_segment(&mut self, index: usize, byte: u8) -> Option<Fragment<'a>> {
+        self.stashed_byte = Some((index, byte));
+        self.uncount_column();
+        let token = Token::TextSegment(self.token.string(self.bytes));
+        self.token.clear();
+        self.frame_token(token)
+    }
+
+    #[inline]
+    fn make_left(&mut self, index: usize, delimiter: ListDelimiter) -> Option<Fragment<'a>> {
+        self.start = self.here_at(index);
+        if self.options.angle_lists {
+            self.open_lists.push(delimiter);
+        }
+        self.frame_token(Token::Left(delimiter))
+    }
+
+    #[inline]
+    fn make_right(&mut self, index: usize, delimiter: ListDelimiter) -> Option<Fragment<'a>> {
+        self.start = self.here_at(index);
+        if self.options.angle_lists {
+            self.open_lists.pop();
+        }
+        self.frame_token(Token::Right(delimiter))
+    }
+
+    /// Whether the `<` at the given index opens an angle list, rather than starting an operator.
+    /// It must not be followed by a `=`, a `<`, a digit or whitespace, and a matching `>` must
+    /// follow on the same line, outside of any list opened after it and of any literal. A `>`
+    /// within a symbol (as in `->`) does not match, nor does one after a `)`, `]` or `}` closing
+    /// a list opened before the `<`.
+    fn angle_list_opener(&self, index: usize) -> bool {
+        if matches!(self.bytes.get(index + 1), None | Some(b'=' | b'<' | b'0'..=b'9' | b' ' | b'\t' | b'\n' | b'\r')) {
+            return false
+        }
+        let (mut angles, mut lists) = (1, 0);
+        let mut symbol_start = None;
+        let mut index = index + 1;
+        while let Some(&byte) = self.bytes.get(index) {
+            if line_terminator(&self.bytes[index..]).is_some() {
+                return false
+            }
+            if is_symbol(byte) {
+                match symbol_start {
+                    Some(start) if self.symbol_table.contains(&SymbolString(Cow::Borrowed(&self.bytes[start..=index]))) => {}
+                    _ => symbol_start = Some(index)
+                }
+            } else {
+                symbol_start = None;
+            }
+            let starts_symbol = symbol_start == Some(index);
+            match byte {
+                b'(' | b'[' | b'{' => lists += 1,
+                b')' | b']' | b'}' if lists == 0 => return false,
+                b')' | b']' | b'}' => lists -= 1,
+                b'<' if lists == 0 && starts_symbol && self.bytes[index - 1].is_ascii_alphanumeric() => angles += 1,
+                b'>' if lists == 0 && starts_symbol => {
+                    angles -= 1;
+                    if angles == 0 {
+                        return true
+                    }
+                }
+                b'"' | b'\'' => {
+                    index += 1;
+                    while let Some(&inner) = self.bytes.get(index) && inner != byte {
+                        if line_terminator(&self.bytes[index..]).is_some() {
+                            return false
+                        }
+                        index += if inner == b'\\' { 2 } else { 1 };
+                    }
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+        false
+    }
+
+    #[inline(always)]
+    fn angle_list_open(&self) -> bool {
+        self.options.angle_lists && self.open_lists.last() == Some(&ListDelimiter::Angle)
+    }
+
+    /// Discards the token in progress after an error, entering a mode that skips the remainder of
+    /// the token if it is not yet complete.
+    fn recover(&mut self) {
+        self.token.clear();
+        self.byte_copy = None;
+        self.sign = None;
+        let resync = if let Some((index, b'\n')) = self.byte_indexes.previous() && self.stashed_byte.is_none() {
+            // the error was detected at the end of the line, which is yet to be framed
+            self.stashed_byte = Some((index, b'\n'));
+            self.uncount_column();
+            self.embeds.clear();
+            None
+        } else {
+            if self.stashed_byte.is_some() {
+                // a number was terminated by the stashed byte, which is yet to be uncounted
+                self.uncount_column();
+            }
+            if !self.embeds.is_empty() || matches!(self.mode, Mode::Segment) {
+                self.embeds.clear();
+                Some(Resync::Line)
+            } else if self.stashed_byte.is_some() {
+                None
+            } else {
+                match self.mode {
+                    Mode::Text | Mode::ByteText(_) => Some(Resync::Quote(b'"')),
+                    Mode::Character | Mode::ByteCharacter(_) => Some(Resync::Quote(b'\'')),
+                    Mode::Integer | Mode::RadixInteger(_) | Mode::Decimal(_) | Mode::Exponent(_) => Some(Resync::Token),
+                    _ => None
+                }
+            }
+        };
+        match resync {
+            None => {
+                self.mode = Mode::Whitespace;
+                self.skip_token();
+            }
+            Some(resync) => {
+                self.mode = Mode::Recovery(resync);
+            }
+        }
+    }
+
     fn frame_token(&mut self, token: Token<'a>) -> Option<Fragment<'a>> {
-        let start = Some(self.start.clone());
-        self.start = self.location.clone();
-        self.start.column += 1;
-        let end = Some(self.location.clone());
+        let (token, start) = match self.sign.take() {
+            None => (token, Some(self.start.clone())),
+            Some((sign, start)) => (Token::Signed(sign, Box::new(token)), Some(start))
+        };
+        if !matches!(token, Token::Comment(_)) {
+            self.operand_last = is_operand(&token);
+        }
+        self.skip_token();
+        let end = Some(self.here());
         Some(Ok((token, Metadata { start, end })))
     }
+
+    #[inline(always)]
+    fn skip_token(&mut self) {
+        self.start = self.here();
+        self.start.column += 1;
+    }
 }
 
 pub type Fragment<'a> = Result<(Token<'a>, Metadata), Box<Error>>;
@@ -300,62 +1203,112 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
         if self.error {
             return None;
         }
+        if let Some(err) = self.pending_error.take() {
+            self.error = true;
+            return Some(Err(err))
+        }
+
+        let fragment = self.advance();
+        if self.error && self.options.recover {
+            self.error = false;
+            self.recover();
+        }
+        fragment
+    }
+}
 
+impl<'a> Tokeniser<'a, '_> {
+    fn advance(&mut self) -> Option<Fragment<'a>> {
         while let Some((index, byte)) = self.next_byte() {
-            self.location.column += 1;
+            self.count_column(byte);
             match self.mode {
                 Mode::Whitespace => {
+                    let ident_attached = mem::take(&mut self.ident_attached);
                     match byte {
                         b'\\' => {
                             self.error = true;
-                            return Some(Err(Error::UnexpectedCharacter(byte as char, self.location.clone()).into()))
+                            return Some(Err(Error::UnexpectedCharacter(byte as char, self.here()).into()))
                         }
                         b'"' => {
-                            self.start = self.location.clone();
-                            self.mode = Mode::Text;
+                            self.start = self.here_at(index);
+                            if self.starts_with(index, TEXT_BLOCK_FENCE) {
+                                self.skip_sequence(TEXT_BLOCK_FENCE.len());
+                                self.mode = Mode::TextBlock(index + TEXT_BLOCK_FENCE.len());
+                            } else if self.options.interpolation && self.interpolated(index) {
+                                self.mode = Mode::Segment;
+                                return self.frame_token(Token::InterpolationBegin)
+                            } else {
+                                self.mode = Mode::Text;
+                            }
                         }
                         b'\'' => {
-                            self.start = self.location.clone();
+                            self.start = self.here_at(index);
                             self.mode = Mode::Character;
                         }
-                        b'\t' | b'\r' | b' ' => {}
+                        b'\t' | b' ' => {}
+                        b'\n' if !self.embeds.is_empty() => {
+                            self.error = true;
+                            return Some(Err(Error::UnterminatedLiteral(self.here()).into()))
+                        }
                         b'\n' => {
                             self.location.line += 1;
                             self.location.column = 0;
                             return self.frame_token(Token::Newline)
                         }
                         b'(' => {
-                            self.start = self.location.clone();
-                            return self.frame_token(Token::Left(ListDelimiter::Paren));
+                            return self.make_left(index, ListDelimiter::Paren);
                         }
                         b')' => {
-                            self.start = self.location.clone();
-                            return self.frame_token(Token::Right(ListDelimiter::Paren));
+                            return self.make_right(index, ListDelimiter::Paren);
                         }
                         b'{' => {
-                            self.start = self.location.clone();
-                            return self.frame_token(Token::Left(ListDelimiter::Brace));
+                            if let Some(braces) = self.embeds.last_mut() {
+                                *braces += 1;
+                            }
+                            return self.make_left(index, ListDelimiter::Brace);
+                        }
+                        b'}' if self.embeds.last() == Some(&0) => {
+                            self.embeds.pop();
+                            self.start = self.here_at(index);
+                            self.mode = Mode::Segment;
+                            return self.frame_token(Token::EmbedEnd)
                         }
                         b'}' => {
-                            self.start = self.location.clone();
-                            return self.frame_token(Token::Right(ListDelimiter::Brace));
+                            if let Some(braces) = self.embeds.last_mut() {
+                                *braces -= 1;
+                            }
+                            return self.make_right(index, ListDelimiter::Brace);
                         }
                         b'[' => {
-                            self.start = self.location.clone();
-                            return self.frame_token(Token::Left(ListDelimiter::Bracket));
+                            return self.make_left(index, ListDelimiter::Bracket);
                         }
                         b']' => {
-                            self.start = self.location.clone();
-                            return self.frame_token(Token::Right(ListDelimiter::Bracket));
+                            return self.make_right(index, ListDelimiter::Bracket);
+                        }
+                        b'<' if ident_attached && self.options.angle_lists && self.angle_list_opener(index) => {
+                            return self.make_left(index, ListDelimiter::Angle);
+                        }
+                        b'>' if self.angle_list_open() => {
+                            return self.make_right(index, ListDelimiter::Angle);
                         }
                         b'0'..=b'9' => {
                             self.mode = Mode::Integer;
-                            self.start = self.location.clone();
+                            self.start = self.here_at(index);
                             self.token.push_byte(index, byte);
                         }
                         _ => {
                             if is_symbol(byte) {
-                                self.start = self.location.clone();
+                                self.start = self.here_at(index);
+                                if let Some((mode, opener_len)) = self.comment_opener(index) {
+                                    self.skip_sequence(opener_len);
+                                    self.mode = mode;
+                                    continue;
+                                }
+                                if matches!(byte, b'-' | b'+') && self.sign_attached(index) {
+                                    let sign = if byte == b'-' { Sign::Minus } else { Sign::Plus };
+                                    self.sign = Some((sign, self.here_at(index)));
+                                    continue;
+                                }
                                 self.token.push_byte(index, byte);
                                 match self.parse_symbol() {
                                     None => {}
@@ -363,13 +1316,22 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                                         return self.frame_token(token)
                                     }
                                 }
+                            } else if byte == b'b' && let Some(&quote @ (b'"' | b'\'')) = self.bytes.get(index + 1) {
+                                self.start = self.here_at(index);
+                                self.skip_sequence(2);
+                                self.mode = if quote == b'"' { Mode::ByteText(index + 2) } else { Mode::ByteCharacter(None) };
+                            } else if byte == b'r' && let Some(hashes) = self.raw_text_opener(index) {
+                                self.start = self.here_at(index);
+                                self.skip_sequence(hashes + 2);
+                                self.mode = Mode::RawText(hashes, index + hashes + 2);
                             } else {
-                                self.start = self.location.clone();
+                                self.start = self.here_at(index);
                                 self.mode = Mode::Ident;
                                 if byte < 0x80 {
                                     self.token.push_byte(index, byte);
                                 } else {
-                                    self.token.push_grapheme(index, read_grapheme(byte, &mut self.byte_indexes))
+                                    let grapheme = self.read_grapheme(byte);
+                                    self.token.push_grapheme(index, grapheme)
                                 }
                             }
                         }
@@ -381,7 +1343,9 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                             match self.parse_escape() {
                                 Ok(char) => {
                                     self.token.copy(self.bytes);
-                                    self.token.push_char(0, char);
+                                    if let Some(char) = char {
+                                        self.token.push_char(0, char);
+                                    }
                                 }
                                 Err(err) => {
                                     return Some(Err(err))
@@ -396,13 +1360,104 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                         }
                         b'\n' => {
                             self.error = true;
-                            return Some(Err(Error::UnterminatedLiteral(self.location.clone()).into()))
+                            return Some(Err(Error::UnterminatedLiteral(self.here()).into()))
+                        }
+                        _ => {
+                            if byte < 0x80 {
+                                self.token.push_byte(index, byte);
+                            } else {
+                                let grapheme = self.read_grapheme(byte);
+                                self.token.push_grapheme(index, grapheme)
+                            }
+                        }
+                    }
+                }
+                Mode::Segment => {
+                    match byte {
+                        b'$' if self.bytes.get(index + 1) == Some(&b'{') => {
+                            if !self.token.is_empty() {
+                                return self.make_segment(index, byte)
+                            }
+                            self.start = self.here_at(index);
+                            self.skip_sequence(2);
+                            self.embeds.push(0);
+                            self.mode = Mode::Whitespace;
+                            return self.frame_token(Token::EmbedBegin)
+                        }
+                        b'"' => {
+                            if !self.token.is_empty() {
+                                return self.make_segment(index, byte)
+                            }
+                            self.start = self.here_at(index);
+                            self.mode = Mode::Whitespace;
+                            return self.frame_token(Token::InterpolationEnd)
+                        }
+                        b'\\' => {
+                            match self.parse_escape() {
+                                Ok(char) => {
+                                    self.token.copy(self.bytes);
+                                    if let Some(char) = char {
+                                        self.token.push_char(0, char);
+                                    }
+                                }
+                                Err(err) => {
+                                    return Some(Err(err))
+                                }
+                            }
+                        }
+                        b'\n' => {
+                            self.error = true;
+                            return Some(Err(Error::UnterminatedLiteral(self.here()).into()))
                         }
                         _ => {
                             if byte < 0x80 {
                                 self.token.push_byte(index, byte);
                             } else {
-                                self.token.push_grapheme(index, read_grapheme(byte, &mut self.byte_indexes))
+                                let grapheme = self.read_grapheme(byte);
+                                self.token.push_grapheme(index, grapheme)
+                            }
+                        }
+                    }
+                }
+                Mode::RawText(hashes, offset) => {
+                    match byte {
+                        b'"' if self.raw_text_closer(index, hashes) => {
+                            self.skip_sequence(hashes + 1);
+                            let str = unsafe { str::from_utf8_unchecked(&self.bytes[offset..index]) };
+                            self.mode = Mode::Whitespace;
+                            return self.frame_token(Token::Text(Cow::Borrowed(str)))
+                        }
+                        b'\n' => {
+                            self.error = true;
+                            let closer = format!("\"{}", "#".repeat(hashes));
+                            return Some(Err(Error::UnterminatedRawLiteral(closer, self.start.clone()).into()))
+                        }
+                        _ => {
+                            if byte >= 0x80 {
+                                self.read_grapheme(byte);
+                            }
+                        }
+                    }
+                }
+                Mode::TextBlock(offset) => {
+                    match byte {
+                        b'"' if self.starts_with(index, TEXT_BLOCK_FENCE) => {
+                            self.skip_sequence(TEXT_BLOCK_FENCE.len());
+                            let str = unsafe { str::from_utf8_unchecked(&self.bytes[offset..index]) };
+                            let text = match normalise_line_endings(str) {
+                                Cow::Borrowed(str) => dedent(str),
+                                Cow::Owned(string) => Cow::Owned(dedent(&string).into_owned())
+                            };
+                            self.mode = Mode::Whitespace;
+                            return self.frame_token(Token::Text(text))
+                        }
+                        b'\n' => {
+                            self.location.line += 1;
+                            self.location.column = 0;
+                        }
+                        _ => {
+                            if byte >= 0x80 {
+                                self.read_grapheme(byte);
                             }
                         }
                     }
@@ -413,7 +1468,9 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                             match self.parse_escape() {
                                 Ok(char) => {
                                     self.token.copy(self.bytes);
-                                    self.token.push_char(0, char);
+                                    if let Some(char) = char {
+                                        self.token.push_char(0, char);
+                                    }
                                 }
                                 Err(err) => {
                                     return Some(Err(err))
@@ -425,7 +1482,8 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                             return match chars.next() {
                                 None => {
                                     self.error = true;
-                                    Some(Err(Error::EmptyCharacterLiteral(self.location.clone()).into()))
+                                    self.mode = Mode::Whitespace; // the literal is closed
+                                    Some(Err(Error::EmptyCharacterLiteral(self.here()).into()))
                                 }
                                 Some(first_char) => {
                                     let token = Token::Character(first_char);
@@ -437,19 +1495,91 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                         }
                         b'\n' => {
                             self.error = true;
-                            return Some(Err(Error::UnterminatedLiteral(self.location.clone()).into()))
+                            return Some(Err(Error::UnterminatedLiteral(self.here()).into()))
                         }
                         _ => {
                             if self.token.is_empty() {
                                 if byte < 0x80 {
                                     self.token.push_byte(index, byte);
                                 } else {
-                                    self.token.push_grapheme(index, read_grapheme(byte, &mut self.byte_indexes))
+                                    let grapheme = self.read_grapheme(byte);
+                                    self.token.push_grapheme(index, grapheme)
                                 }
                             } else {
                                 self.error = true;
-                                return Some(Err(Error::UnexpectedCharacter(byte as char, self.location.clone()).into()))
+                                return Some(Err(Error::UnexpectedCharacter(byte as char, self.here()).into()))
+                            }
+                        }
+                    }
+                }
+                Mode::ByteText(offset) => {
+                    match byte {
+                        b'\\' => {
+                            match self.parse_byte_escape() {
+                                Ok(escaped) => {
+                                    self.byte_copy.get_or_insert_with(|| self.bytes[offset..index].to_vec()).push(escaped);
+                                }
+                                Err(err) => {
+                                    return Some(Err(err))
+                                }
+                            }
+                        }
+                        b'"' => {
+                            let bytes = match self.byte_copy.take() {
+                                None => Cow::Borrowed(&self.bytes[offset..index]),
+                                Some(copy) => Cow::Owned(copy)
+                            };
+                            self.mode = Mode::Whitespace;
+                            return self.frame_token(Token::Bytes(bytes))
+                        }
+                        b'\n' => {
+                            self.error = true;
+                            return Some(Err(Error::UnterminatedLiteral(self.here()).into()))
+                        }
+                        _ => {
+                            if byte >= 0x80 {
+                                return Some(self.unexpected_character(byte))
+                            }
+                            if let Some(copy) = &mut self.byte_copy {
+                                copy.push(byte);
+                            }
+                        }
+                    }
+                }
+                Mode::ByteCharacter(value) => {
+                    match byte {
+                        b'\\' if value.is_none() => {
+                            match self.parse_byte_escape() {
+                                Ok(escaped) => {
+                                    self.mode = Mode::ByteCharacter(Some(escaped));
+                                }
+                                Err(err) => {
+                                    return Some(Err(err))
+                                }
+                            }
+                        }
+                        b'\'' => {
+                            return match value {
+                                None => {
+                                    self.error = true;
+                                    self.mode = Mode::Whitespace; // the literal is closed
+                                    Some(Err(Error::EmptyCharacterLiteral(self.here()).into()))
+                                }
+                                Some(value) => {
+                                    self.mode = Mode::Whitespace;
+                                    self.frame_token(Token::Byte(value))
+                                }
+                            }
+                        }
+                        b'\n' => {
+                            self.error = true;
+                            return Some(Err(Error::UnterminatedLiteral(self.here()).into()))
+                        }
+                        _ => {
+                            if value.is_some() || byte >= 0x80 {
+                                return Some(self.unexpected_character(byte))
                             }
+                            self.mode = Mode::ByteCharacter(Some(byte));
                         }
                     }
                 }
@@ -460,18 +1590,43 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                         }
                         b'.' => {
                             let str = self.token.as_str(self.bytes);
-                            match u128::from_str(str) {
-                                Ok(int) => {
-                                    self.mode = Mode::Decimal(int);
+                            match parse_digits(str, 10) {
+                                Ok(_) => {
+                                    self.mode = Mode::Decimal(self.token.len());
+                                    self.token.push_byte(index, byte);
+                                }
+                                Err(err) => {
+                                    self.error = true;
+                                    return Some(Err(Error::UnparsableInteger(str.to_string(), err, self.here()).into()))
+                                }
+                            }
+                        }
+                        b'e' | b'E' => {
+                            let str = self.token.as_str(self.bytes);
+                            match parse_digits(str, 10) {
+                                Ok(Some(int)) => {
+                                    self.mode = Mode::Exponent(Significand::Decimal(token::Decimal(int, 0, 0)));
+                                    self.token.clear()
+                                }
+                                Ok(None) => {
+                                    self.mode = Mode::Exponent(Significand::Big(str.to_string(), String::new()));
                                     self.token.clear()
                                 }
                                 Err(err) => {
                                     self.error = true;
-                                    return Some(Err(Error::UnparsableInteger(str.to_string(), err, self.location.clone()).into()))
+                                    return Some(Err(Error::UnparsableInteger(str.to_string(), err, self.here()).into()))
                                 }
                             }
                         }
-                        b')' | b']' | b'}' | b'\n' | b'\t' | b'\r' | b' ' => {
+                        b'x' | b'X' | b'o' | b'O' | b'b' | b'B' if self.token.as_str(self.bytes) == "0" => {
+                            self.mode = Mode::RadixInteger(match byte {
+                                b'x' | b'X' => Radix::Hexadecimal,
+                                b'o' | b'O' => Radix::Octal,
+                                _ => Radix::Binary
+                            });
+                            self.token.clear();
+                        }
+                        b')' | b']' | b'}' | b'\n' | b'\t' | b' ' => {
                             self.stashed_byte = Some((index, byte)); // don't consume the char
                             return self.make_integer();
                         }
@@ -484,37 +1639,106 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                                     self.token.push_byte(index, byte);
                                 }
                             } else {
-                                self.token.push_grapheme(index, read_grapheme(byte, &mut self.byte_indexes))
+                                let grapheme = self.read_grapheme(byte);
+                                self.token.push_grapheme(index, grapheme)
+                            }
+                        }
+                    }
+                }
+                Mode::RadixInteger(radix) => {
+                    match byte {
+                        b'_' => {
+                            self.token.copy(self.bytes);
+                        }
+                        b')' | b']' | b'}' | b'\n' | b'\t' | b' ' => {
+                            self.stashed_byte = Some((index, byte)); // don't consume the char
+                            return self.make_radix_integer(radix)
+                        }
+                        _ => {
+                            if byte < 0x80 {
+                                if is_symbol(byte) {
+                                    self.stashed_byte = Some((index, byte)); // don't consume the char
+                                    return self.make_radix_integer(radix)
+                                } else {
+                                    self.token.push_byte(index, byte);
+                                }
+                            } else {
+                                let grapheme = self.read_grapheme(byte);
+                                self.token.push_grapheme(index, grapheme)
+                            }
+                        }
+                    }
+                }
+                Mode::Decimal(point) => {
+                    match byte {
+                        b'_' => {
+                            self.token.copy(self.bytes);
+                        }
+                        b'e' | b'E' => {
+                            match self.parse_decimal(point) {
+                                Ok(Token::Decimal(decimal)) => {
+                                    self.token.clear();
+                                    self.mode = Mode::Exponent(Significand::Decimal(decimal));
+                                }
+                                Ok(Token::BigDecimal(BigDecimal(whole, fractional))) => {
+                                    self.mode = Mode::Exponent(Significand::Big(whole.into_owned(), fractional.into_owned()));
+                                    self.token.clear();
+                                }
+                                Ok(_) => unreachable!(), // a decimal is parsed as either kind
+                                Err(err) => {
+                                    return Some(Err(err))
+                                }
+                            }
+                        }
+                        b')' | b']' | b'}' | b'\n' | b'\t' | b' ' => {
+                            self.stashed_byte = Some((index, byte)); // don't consume the char
+                            return self.make_decimal(point)
+                        }
+                        _ => {
+                            if byte < 0x80 {
+                                if is_symbol(byte) {
+                                    self.stashed_byte = Some((index, byte)); // don't consume the char
+                                    return self.make_decimal(point)
+                                } else {
+                                    self.token.push_byte(index, byte);
+                                }
+                            } else {
+                                let grapheme = self.read_grapheme(byte);
+                                self.token.push_grapheme(index, grapheme)
                             }
                         }
                     }
                 }
-                Mode::Decimal(whole) => {
+                Mode::Exponent(ref significand) => {
                     match byte {
                         b'_' => {
                             self.token.copy(self.bytes);
                         }
-                        b')' | b']' | b'}' | b'\n' | b'\t' | b'\r' | b' ' => {
+                        b'+' | b'-' if self.token.is_empty() => {
+                            self.token.push_byte(index, byte);
+                        }
+                        b')' | b']' | b'}' | b'\n' | b'\t' | b' ' => {
                             self.stashed_byte = Some((index, byte)); // don't consume the char
-                            return self.make_decimal(whole)
+                            return self.make_scientific(significand.clone())
                         }
                         _ => {
                             if byte < 0x80 {
                                 if is_symbol(byte) {
                                     self.stashed_byte = Some((index, byte)); // don't consume the char
-                                    return self.make_decimal(whole)
+                                    return self.make_scientific(significand.clone())
                                 } else {
                                     self.token.push_byte(index, byte);
                                 }
                             } else {
-                                self.token.push_grapheme(index, read_grapheme(byte, &mut self.byte_indexes))
+                                let grapheme = self.read_grapheme(byte);
+                                self.token.push_grapheme(index, grapheme)
                             }
                         }
                     }
                 }
                 Mode::Ident => {
                     match byte {
-                        b')' | b']' | b'}' | b'\n' | b'\t' | b'\r' | b' ' => {
+                        b')' | b']' | b'}' | b'\n' | b'\t' | b' ' => {
                             self.stashed_byte = Some((index, byte)); // don't consume the char
                             return self.make_ident()
                         }
@@ -527,7 +1751,8 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                                     self.token.push_byte(index, byte);
                                 }
                             } else {
-                                self.token.push_grapheme(index, read_grapheme(byte, &mut self.byte_indexes))
+                                let grapheme = self.read_grapheme(byte);
+                                self.token.push_grapheme(index, grapheme)
                             }
 
                             // if is_symbol(byte) {
@@ -541,12 +1766,237 @@ impl<'a> Iterator for Tokeniser<'a, '_> {
                         }
                     }
                 }
+                Mode::Recovery(resync) => {
+                    match (resync, byte) {
+                        (_, b'\n') => {
+                            self.stashed_byte = Some((index, byte)); // don't consume the char
+                            self.uncount_column();
+                            self.mode = Mode::Whitespace;
+                            self.skip_token();
+                        }
+                        (Resync::Quote(quote), b'\\') => {
+                            self.mode = Mode::Recovery(Resync::Escaped(quote));
+                        }
+                        (Resync::Escaped(quote), _) => {
+                            if byte >= 0x80 {
+                                self.read_grapheme(byte);
+                            }
+                            self.mode = Mode::Recovery(Resync::Quote(quote));
+                        }
+                        (Resync::Quote(quote), _) if byte == quote => {
+                            self.mode = Mode::Whitespace;
+                            self.skip_token();
+                        }
+                        (Resync::Token, b'(' | b')' | b'[' | b']' | b'{' | b'}' | b'"' | b'\'' | b'\t' | b' ') => {
+                            self.stashed_byte = Some((index, byte)); // don't consume the char
+                            self.uncount_column();
+                            self.mode = Mode::Whitespace;
+                            self.skip_token();
+                        }
+                        (Resync::Token, _) if is_symbol(byte) => {
+                            self.stashed_byte = Some((index, byte)); // don't consume the char
+                            self.uncount_column();
+                            self.mode = Mode::Whitespace;
+                            self.skip_token();
+                        }
+                        _ => {
+                            if byte >= 0x80 {
+                                self.read_grapheme(byte);
+                            }
+                        }
+                    }
+                }
+                Mode::LineComment(offset) => {
+                    match byte {
+                        b'\n' => {
+                            self.stashed_byte = Some((index, byte)); // don't consume the char
+                            self.uncount_column();
+                            if let Some(fragment) = self.make_comment(offset, index) {
+                                return Some(fragment)
+                            }
+                        }
+                        _ => {
+                            if byte >= 0x80 {
+                                self.read_grapheme(byte);
+                            }
+                        }
+                    }
+                }
+                Mode::BlockComment(offset, depth) => {
+                    let (open, close) = self.options.comments.block.as_ref().unwrap();
+                    let (open_len, close_len) = (open.len(), close.len());
+                    if byte == b'\n' {
+                        self.location.line += 1;
+                        self.location.column = 0;
+                    } else if self.starts_with(index, close) {
+                        self.skip_sequence(close_len);
+                        if depth == 1 {
+                            if let Some(fragment) = self.make_comment(offset, index + close_len) {
+                                return Some(fragment)
+                            }
+                        } else {
+                            self.mode = Mode::BlockComment(offset, depth - 1);
+                        }
+                    } else if self.starts_with(index, open) {
+                        self.skip_sequence(open_len);
+                        self.mode = Mode::BlockComment(offset, depth + 1);
+                    } else if byte >= 0x80 {
+                        self.read_grapheme(byte);
+                    }
+                }
+            }
+        }
+
+        if !self.partial {
+            match self.mode {
+                Mode::BlockComment(_, _) => {
+                    self.error = true;
+                    return Some(Err(Error::UnterminatedComment(self.start.clone()).into()))
+                }
+                Mode::TextBlock(_) => {
+                    self.error = true;
+                    return Some(Err(Error::UnterminatedLiteral(self.start.clone()).into()))
+                }
+                Mode::Text | Mode::Character | Mode::Segment => {
+                    // a line continuation swallowed the final line break
+                    self.error = true;
+                    return Some(Err(Error::UnterminatedLiteral(self.here()).into()))
+                }
+                _ => {}
             }
         }
         None
     }
 }
 
+/// Strips the common indentation from the lines of a text block: the longest run of leading
+/// blanks, byte for byte, that the lines share, so that a tab never stands in for spaces. A line
+/// break directly after the opening fence is dropped, as is one directly before a closing fence
+/// on its own line; the indentation of the closing fence nonetheless counts towards the common
+/// indentation, so that it can be used to retain some of the indentation in the text. Lines
+/// comprising only blanks do not count, and lose as much of the indentation as they share.
+fn dedent(content: &str) -> Cow<'_, str> {
+    let mut body = content;
+    if let Some((first, rest)) = body.split_once('\n') && first.bytes().all(is_blank) {
+        body = rest;
+    }
+    let mut closing_indent = None;
+    if let Some((rest, last)) = body.rsplit_once('\n') && last.bytes().all(is_blank) {
+        body = rest;
+        closing_indent = Some(last);
+    }
+    let indent = body.split('\n')
+        .filter(|line| !line.bytes().all(is_blank))
+        .map(|line| &line[..line.bytes().take_while(|&byte| is_blank(byte)).count()])
+        .chain(closing_indent)
+        .reduce(|indent, line_indent| {
+            let common = indent.bytes().zip(line_indent.bytes()).take_while(|(a, b)| a == b).count();
+            &indent[..common]
+        })
+        .unwrap_or("");
+    if indent.is_empty() {
+        Cow::Borrowed(body)
+    } else {
+        Cow::Owned(body.split('\n').map(|line| strip_indent(line, indent)).collect::<Vec<_>>().join("\n"))
+    }
+}
+
+/// Strips the indentation from a line, or as much of it as the line starts with.
+fn strip_indent<'a>(line: &'a str, indent: &str) -> &'a str {
+    let common = line.bytes().zip(indent.bytes()).take_while(|(a, b)| a == b).count();
+    &line[common..]
+}
+
+/// Replaces every line terminator (see [`LineEnding`]) in the content with a `\n`.
+fn normalise_line_endings(content: &str) -> Cow<'_, str> {
+    const TERMINATORS: [char; 4] = ['\r', '\u{85}', '\u{2028}', '\u{2029}'];
+    if content.contains(TERMINATORS) {
+        Cow::Owned(content.replace("\r\n", "\n").replace(TERMINATORS, "\n"))
+    } else {
+        Cow::Borrowed(content)
+    }
+}
+
+/// Moves the decimal point of a significand, given as validated whole and fractional digits, by
+/// the exponent, yielding a [`BigDecimal`], or a [`token::Decimal`] if both parts now fit.
+fn expand_scientific(whole: &str, fractional: &str, exponent: i32) -> Token<'static> {
+    let digits = [whole, fractional].concat();
+    let point = whole.len() as i64 + exponent as i64;
+    let (whole, fractional) = if point <= 0 {
+        ("0".to_string(), "0".repeat(point.unsigned_abs() as usize) + &digits)
+    } else if point as usize >= digits.len() {
+        (digits.clone() + &"0".repeat(point as usize - digits.len()), String::new())
+    } else {
+        let (whole, fractional) = digits.split_at(point as usize);
+        (whole.to_string(), fractional.to_string())
+    };
+    match (parse_digits(&whole, 10), parse_digits(&fractional, 10)) {
+        (Ok(Some(whole)), Ok(Some(fractional_value))) if fractional.len() <= u8::MAX as usize => {
+            Token::Decimal(token::Decimal(whole, fractional_value, fractional.len() as u8))
+        }
+        (Ok(Some(whole)), _) if fractional.is_empty() => Token::Decimal(token::Decimal(whole, 0, 0)),
+        _ => {
+            let fractional = if fractional.is_empty() { "0".to_string() } else { fractional };
+            Token::BigDecimal(BigDecimal(Cow::Owned(whole), Cow::Owned(fractional)))
+        }
+    }
+}
+
+/// Maps a single-character escape sequence to the escape and the byte it denotes.
+#[inline]
+fn simple_escape(byte: u8) -> Option<(Escape, u8)> {
+    match byte {
+        b'\\' => Some((Escape::Backslash, b'\\')),
+        b'"' => Some((Escape::Quote, b'"')),
+        b'\'' => Some((Escape::Apostrophe, b'\'')),
+        b'/' => Some((Escape::Slash, b'/')),
+        b'0' => Some((Escape::Nul, 0)),
+        b'a' => Some((Escape::Bell, 0x07)),
+        b'b' => Some((Escape::Backspace, 0x08)),
+        b't' => Some((Escape::Tab, b'\t')),
+        b'n' => Some((Escape::Newline, b'\n')),
+        b'v' => Some((Escape::VerticalTab, 0x0B)),
+        b'f' => Some((Escape::FormFeed, 0x0C)),
+        b'r' => Some((Escape::CarriageReturn, b'\r')),
+        b'e' => Some((Escape::Esc, 0x1B)),
+        b'$' => Some((Escape::Dollar, b'$')),
+        _ => None
+    }
+}
+
+/// Whether the token may be the left operand of a binary operator.
+#[inline]
+fn is_operand(token: &Token) -> bool {
+    !matches!(
+        token,
+        Token::Left(_) | Token::Symbol(_) | Token::ExtendedSymbol(_) | Token::Newline | Token::InterpolationBegin | Token::TextSegment(_) | Token::EmbedBegin
+    )
+}
+
+/// Whitespace that does not end a line. Never part of a valid escape sequence, it is where partial
+/// input may be safely split.
+#[inline(always)]
+pub(crate) fn is_blank(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t')
+}
+
+/// Parses digits in the given base, yielding `None` for a valid number that is too large to fit
+/// in a `u128`.
+#[inline]
+fn parse_digits(str: &str, base: u32) -> Result<Option<u128>, ParseIntError> {
+    match u128::from_str_radix(str, base) {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if *err.kind() == IntErrorKind::PosOverflow => {
+            // overflow may be reported ahead of an invalid digit further along
+            match str.chars().find(|char| !char.is_digit(base)) {
+                None => Ok(None),
+                Some(char) => Err(u8::from_str_radix(char.encode_utf8(&mut [0; 4]), base).unwrap_err())
+            }
+        }
+        Err(err) => Err(err)
+    }
+}
+
 #[inline(never)]
 pub fn read_grapheme(b0: u8, bytes: &mut NewlineTerminatedBytes) -> Grapheme {
     __read_grapheme(b0, bytes).unwrap()
@@ -568,5 +2018,7 @@ fn __read_grapheme(b0: u8, bytes: &mut NewlineTerminatedBytes) -> Option<Graphem
     }
 }
 
+pub mod stream;
+
 #[cfg(test)]
 mod tests;
\ No newline at end of file
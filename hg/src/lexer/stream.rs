@@ -0,0 +1,518 @@
+use std::collections::VecDeque;
+use std::io;
+use std::io::BufRead;
+use crate::lexer::{is_blank, Checkpoint, Error, Fragment, Options, Tokeniser};
+use crate::symbols::SymbolTable;
+use crate::token::LineEnding;
+
+/// Tokenises input that arrives piecemeal, carrying the [`Tokeniser`] state across pieces.
+/// Buffered input is tokenised up to its last blank or line terminator (see [`LineEnding`]), where
+/// no token other than one that may contain whitespace (such as a text literal or a comment) can
+/// be cut short. Such a token is kept in the buffer until it is complete. With
+/// [`Options::angle_lists`], input is only tokenised up to its last line terminator, as telling
+/// whether a `<` opens an angle list takes the rest of its line.
+struct Segments<'s> {
+    symbol_table: SymbolTable<'s>,
+    options: Options,
+    buffer: Vec<u8>,
+    checkpoint: Checkpoint,
+    fragments: VecDeque<Fragment<'static>>,
+    line_ending: Option<LineEnding>,
+    /// Whether the input tokenised so far ends with a line break.
+    terminated: bool,
+    error: bool,
+}
+
+impl<'s> Segments<'s> {
+    fn new(symbol_table: SymbolTable<'s>, options: Options) -> Self {
+        Self {
+            symbol_table,
+            options,
+            buffer: vec![],
+            checkpoint: Checkpoint::before_start(),
+            fragments: VecDeque::new(),
+            line_ending: None,
+            terminated: false,
+            error: false,
+        }
+    }
+
+    /// Tokenises the buffer up to its last blank (unless angle lists are enabled) or line
+    /// terminator or, if `last` is set, the entire buffer, queueing the resulting fragments. A carriage return ending the buffer is not
+    /// cut at, as a line feed may yet follow it.
+    fn tokenise(&mut self, last: bool) {
+        if self.error {
+            return
+        }
+
+        let end = if last {
+            if self.buffer.is_empty() && self.terminated {
+                return
+            }
+            self.buffer.len()
+        } else {
+            let len = match self.buffer.last() {
+                Some(b'\r') => self.buffer.len() - 1,
+                _ => self.buffer.len()
+            };
+            let blanks = !self.options.angle_lists;
+            match (0..len).rev().find(|&index| blanks && is_blank(self.buffer[index]) || ends_line_terminator(&self.buffer, index)) {
+                None => return,
+                Some(index) => index + 1
+            }
+        };
+
+        let str = match str::from_utf8(&self.buffer[..end]) {
+            Ok(str) => str,
+            Err(err) => {
+                self.error = true;
+                let err = io::Error::new(io::ErrorKind::InvalidData, err);
+                self.fragments.push_back(Err(Error::Io(err).into()));
+                return
+            }
+        };
+
+        let mut tokeniser = Tokeniser::resume(str, self.symbol_table.borrowed(), self.options.clone(), self.checkpoint.clone(), !last);
+        while let Some(fragment) = tokeniser.next() {
+            match fragment {
+                Ok((token, metadata)) => {
+                    self.fragments.push_back(Ok((token.into_owned(), metadata)));
+                    self.checkpoint = tokeniser.checkpoint();
+                }
+                Err(err) => {
+                    self.fragments.push_back(Err(err));
+                    if !self.options.recover {
+                        self.error = true;
+                        return
+                    }
+                    self.checkpoint = tokeniser.checkpoint();
+                }
+            }
+        }
+        if !last && tokeniser.resumable() {
+            // skip the whitespace or discarded comment consumed since the last token next time
+            self.checkpoint = tokeniser.checkpoint();
+        }
+        self.line_ending = self.line_ending.or(tokeniser.line_ending());
+
+        if self.checkpoint.offset > 0 {
+            self.terminated = ends_line_terminator(&self.buffer, self.checkpoint.offset - 1);
+        }
+        self.buffer.drain(..self.checkpoint.offset);
+        self.checkpoint.offset = 0;
+    }
+}
+
+/// Whether the byte at the given index is the last of a line terminator (see [`LineEnding`]).
+#[inline]
+fn ends_line_terminator(bytes: &[u8], index: usize) -> bool {
+    match bytes[index] {
+        b'\n' | b'\r' => true,
+        0x85 => bytes[..index].ends_with(&[0xC2]),
+        0xA8 | 0xA9 => bytes[..index].ends_with(&[0xE2, 0x80]),
+        _ => false
+    }
+}
+
+/// Reads into the buffer up to and including the next byte that may end a line terminator (see
+/// [`LineEnding`]), or to the end of the input, returning the number of bytes read. A byte of a
+/// multibyte character may be taken for the end of a terminator; the line is then merely cut
+/// short.
+fn read_line(reader: &mut impl BufRead, buffer: &mut Vec<u8>) -> io::Result<usize> {
+    let mut read = 0;
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err)
+        };
+        let (len, done) = match available.iter().position(|&byte| matches!(byte, b'\n' | b'\r' | 0x85 | 0xA8 | 0xA9)) {
+            Some(index) => (index + 1, true),
+            None => (available.len(), available.is_empty())
+        };
+        buffer.extend_from_slice(&available[..len]);
+        reader.consume(len);
+        read += len;
+        if done {
+            return Ok(read)
+        }
+    }
+}
+
+/// A tokeniser that incrementally reads from a [`BufRead`], yielding owned tokens. Input is
+/// consumed a line at a time, so memory use is bounded by the longest line (or the longest token,
+/// for tokens that span lines) rather than by the size of the input.
+pub struct StreamTokeniser<'s, R> {
+    reader: R,
+    segments: Segments<'s>,
+    eof: bool,
+}
+
+impl<'s, R: BufRead> StreamTokeniser<'s, R> {
+    #[inline]
+    pub fn new(reader: R, symbol_table: SymbolTable<'s>) -> Self {
+        Self::with_options(reader, symbol_table, Options::default())
+    }
+
+    #[inline]
+    pub fn with_options(reader: R, symbol_table: SymbolTable<'s>, options: Options) -> Self {
+        Self {
+            reader,
+            segments: Segments::new(symbol_table, options),
+            eof: false,
+        }
+    }
+
+    /// The first line terminator read (see [`Tokeniser::line_ending`]).
+    #[inline]
+    pub fn line_ending(&self) -> Option<LineEnding> {
+        self.segments.line_ending
+    }
+}
+
+impl<R: BufRead> Iterator for StreamTokeniser<'_, R> {
+    type Item = Fragment<'static>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(fragment) = self.segments.fragments.pop_front() {
+                return Some(fragment)
+            }
+            if self.segments.error || self.eof {
+                return None
+            }
+            match read_line(&mut self.reader, &mut self.segments.buffer) {
+                Ok(0) => {
+                    self.eof = true;
+                    self.segments.tokenise(true);
+                }
+                Ok(_) => {
+                    self.segments.tokenise(false);
+                }
+                Err(err) => {
+                    self.segments.error = true;
+                    return Some(Err(Error::Io(err).into()))
+                }
+            }
+        }
+    }
+}
+
+/// A push-style tokeniser for input that arrives in arbitrarily sized chunks, such as from a
+/// socket. A chunk may end anywhere, including in the middle of a token or a UTF-8 sequence.
+/// Input is tokenised up to its last blank or line terminator, so a token is only emitted once
+/// followed by whitespace (or at the [`finish`](Self::finish)), even if it could not be extended
+/// anyway: `feed(b"(")` emits nothing until whitespace is fed. With [`Options::angle_lists`],
+/// tokens are only emitted a line at a time.
+pub struct PushTokeniser<'s> {
+    segments: Segments<'s>,
+}
+
+impl<'s> PushTokeniser<'s> {
+    #[inline]
+    pub fn new(symbol_table: SymbolTable<'s>) -> Self {
+        Self::with_options(symbol_table, Options::default())
+    }
+
+    #[inline]
+    pub fn with_options(symbol_table: SymbolTable<'s>, options: Options) -> Self {
+        Self {
+            segments: Segments::new(symbol_table, options),
+        }
+    }
+
+    /// Appends a chunk of input, returning the fragments of the tokens followed by whitespace
+    /// since the last call.
+    pub fn feed(&mut self, chunk: &[u8]) -> impl Iterator<Item = Fragment<'static>> + '_ {
+        if !self.segments.error {
+            self.segments.buffer.extend_from_slice(chunk);
+            self.segments.tokenise(false);
+        }
+        self.segments.fragments.drain(..)
+    }
+
+    /// The first line terminator read (see [`Tokeniser::line_ending`]).
+    #[inline]
+    pub fn line_ending(&self) -> Option<LineEnding> {
+        self.segments.line_ending
+    }
+
+    /// Signals the end of the input, returning the remaining fragments.
+    pub fn finish(mut self) -> impl Iterator<Item = Fragment<'static>> {
+        self.segments.tokenise(true);
+        self.segments.fragments.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::io::{BufReader, Read};
+    use std::num::NonZeroU32;
+    use crate::lexer::stream::{PushTokeniser, StreamTokeniser};
+    use crate::lexer::{Comments, Error, Escape, Escapes, Options, Tokeniser};
+    use crate::metadata::{ColumnUnit, Metadata};
+    use crate::token::{LineEnding, Token};
+    use crate::token::ListDelimiter::{Bracket, Paren};
+    use crate::symbols::SymbolTable;
+
+    fn assert_same_as_slice(str: &str, options: Options) {
+        let expected = Tokeniser::with_options(str, SymbolTable::default(), options.clone())
+            .map(|fragment| fragment.map_err(|err| err.to_string()))
+            .collect::<Vec<_>>();
+        for capacity in [1, 3, 64] {
+            let reader = BufReader::with_capacity(capacity, str.as_bytes());
+            let actual = StreamTokeniser::with_options(reader, SymbolTable::default(), options.clone())
+                .map(|fragment| fragment.map_err(|err| err.to_string()))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, actual, "for input {str:?} and capacity {capacity}");
+        }
+    }
+
+    #[test]
+    fn same_as_slice() {
+        for str in [
+            "",
+            "\n",
+            "\n\n",
+            "hello",
+            "hello\n",
+            "  \n  ",
+            "{\"key\": [1, 2.5, true]}\n(a b)\n",
+            "µℝ💣 \"µℝ💣\" 'µ'",
+            "a /* b\n c /* d\n */\n e */ f\n",
+            "a // b\nc",
+            "1 /* unterminated\n",
+            "\"unterminated\n\"",
+            "a ++ b -= c",
+            "[-1, 2 -3]\n-4 x\n-5 y -6",
+            "r#\"a b \"µ\" d\"# x r\"unterminated \n",
+            "a \"\"\"\n  b c\n    µ\n  \"\"\" d \"\"\" e\n",
+            "b\"a b\\x00 \" b' ' b\"\\x4 \"",
+            "\"a \\\n   b\" '\\\n c' \"\\a\\e\" \"d \\\n",
+            "x \"a b ${ y } c d\" \"${ {1 2} }${\"${z}\"}\" \"e ${\n",
+            "a \"b\\q c\" d 'xy' 1.2.3 e \\ f\n\"g\n0x1g2 h \"${ 1x } i\" j\n",
+            "\ta\t1\tb\n  \t\"c\td\"\t1x\te\n",
+            "a\r\nb \"c\" 1\rd\u{85}e\u{2028}\"\"\"\r\n f\r\n \"\"\" g\u{2029}",
+        ] {
+            assert_same_as_slice(str, Options::default());
+            assert_same_as_slice(str, Options {
+                comments: Comments {
+                    emit: true,
+                    ..Comments::c_style()
+                },
+                angle_lists: true,
+                signed_numbers: true,
+                escapes: Escapes::json().with(Escape::LineContinuation),
+                interpolation: true,
+                column_unit: ColumnUnit::Utf16,
+                tab_width: NonZeroU32::new(4),
+                recover: true,
+            });
+        }
+    }
+
+    #[test]
+    fn lines_ended_by_any_terminator() {
+        for terminator in ["\r", "\u{85}", "\u{2028}", "\u{2029}"] {
+            let str = format!("abc{terminator}").repeat(1000);
+            assert_same_as_slice(&str, Options::default());
+
+            let reader = BufReader::with_capacity(4, str.as_bytes());
+            let mut tokeniser = StreamTokeniser::new(reader, SymbolTable::default());
+            assert!(tokeniser.next().unwrap().is_ok());
+            let read = str.len() - tokeniser.reader.get_ref().len();
+            assert!(read <= 2 * (3 + terminator.len()) + 4, "read {read} bytes for terminator {terminator:?}");
+        }
+    }
+
+    #[test]
+    fn angle_lists_across_lines() {
+        let options = Options {
+            angle_lists: true,
+            ..Options::default()
+        };
+        assert_same_as_slice("Map<K,\nV> > 1\n", options);
+    }
+
+    #[test]
+    fn discarded_comments_not_retained() {
+        let options = Options {
+            comments: Comments::c_style(),
+            ..Options::default()
+        };
+        let line = " lorem ipsum dolor sit amet\n";
+        let str = format!("a /*{}*/ b // {}", line.repeat(100), line.trim_start().repeat(2));
+        assert_same_as_slice(&str, options.clone());
+
+        let mut tokeniser = PushTokeniser::with_options(SymbolTable::default(), options);
+        assert_eq!(1, tokeniser.feed(b"a /*\n").count());
+        for _ in 0..100 {
+            assert_eq!(0, tokeniser.feed(line.as_bytes()).count());
+            assert!(tokeniser.segments.buffer.is_empty());
+        }
+        assert_eq!(1, tokeniser.feed(b"*/ b //").count());
+        for word in line.trim_end().split_inclusive(' ') {
+            assert_eq!(0, tokeniser.feed(word.as_bytes()).count());
+            assert!(tokeniser.segments.buffer.len() <= word.len());
+        }
+        let tokens = tokeniser.finish().map(|fragment| fragment.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(vec![Token::Newline], tokens);
+    }
+
+    #[test]
+    fn invalid_utf8_err() {
+        let bytes: &[u8] = b"a\n\xff\n";
+        let mut tokens = StreamTokeniser::new(bytes, SymbolTable::default());
+        assert!(tokens.next().unwrap().is_ok());
+        assert!(tokens.next().unwrap().is_ok());
+        let err = tokens.next().unwrap().unwrap_err();
+        assert!(matches!(*err, Error::Io(ref err) if err.kind() == io::ErrorKind::InvalidData));
+        assert!(tokens.next().is_none());
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("boom"))
+        }
+    }
+
+    #[test]
+    fn io_err() {
+        let mut tokens = StreamTokeniser::new(BufReader::new(FailingReader), SymbolTable::default());
+        let err = tokens.next().unwrap().unwrap_err();
+        assert_eq!("i/o error boom", err.to_string());
+        assert!(tokens.next().is_none());
+    }
+
+    fn push_in_chunks(str: &str, options: Options, chunk_len: usize) -> Vec<Result<(Token<'static>, Metadata), String>> {
+        let mut tokeniser = PushTokeniser::with_options(SymbolTable::default(), options);
+        let mut fragments = vec![];
+        for chunk in str.as_bytes().chunks(chunk_len) {
+            fragments.extend(tokeniser.feed(chunk));
+        }
+        fragments.extend(tokeniser.finish());
+        fragments.into_iter().map(|fragment| fragment.map_err(|err| err.to_string())).collect()
+    }
+
+    #[test]
+    fn push_same_as_slice() {
+        for str in [
+            "",
+            "hello world",
+            "{\"key\": [1, 2.5, true], \"µℝ💣\": 'µ'}\n(a b)\n",
+            "\"hello wide world\" \"esc\\\"aped \\u{1f4af} \\x7e\"",
+            "a /* b\n c /* d */ e */ f // g h\ni",
+            "a ++ b -= c",
+            "\"\\u{1 2}\"",
+            "\"\\x \"",
+            "1 2.5 .5 3",
+            "a\r\nb\rc \u{2028}d\r\n",
+        ] {
+            for options in [Options::default(), Options { comments: Comments::c_style(), ..Options::default() }] {
+                let expected = Tokeniser::with_options(str, SymbolTable::default(), options.clone())
+                    .map(|fragment| fragment.map(|(token, metadata)| (token.into_owned(), metadata)).map_err(|err| err.to_string()))
+                    .collect::<Vec<_>>();
+                for chunk_len in 1..=str.len().max(1) {
+                    let actual = push_in_chunks(str, options.clone(), chunk_len);
+                    assert_eq!(expected, actual, "for input {str:?} and chunk length {chunk_len}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn push_angle_lists_same_as_slice() {
+        let options = Options {
+            angle_lists: true,
+            ..Options::default()
+        };
+        let str = "a<b c>\nMap<K, Vec<V>> x\r(a<b, c) > d";
+        let expected = Tokeniser::with_options(str, SymbolTable::default(), options.clone())
+            .map(|fragment| fragment.map(|(token, metadata)| (token.into_owned(), metadata)).map_err(|err| err.to_string()))
+            .collect::<Vec<_>>();
+        for chunk_len in 1..=str.len() {
+            assert_eq!(expected, push_in_chunks(str, options.clone(), chunk_len), "for chunk length {chunk_len}");
+        }
+        assert_same_as_slice(str, options);
+    }
+
+    #[test]
+    fn line_ending_recorded() {
+        let str = "a /* b\r\n */ c\nd\n";
+        let reader = BufReader::with_capacity(3, str.as_bytes());
+        let options = Options {
+            comments: Comments::c_style(),
+            ..Options::default()
+        };
+        let mut tokeniser = StreamTokeniser::with_options(reader, SymbolTable::default(), options);
+        assert_eq!(None, tokeniser.line_ending());
+        assert_eq!(5, tokeniser.by_ref().count());
+        assert_eq!(Some(LineEnding::CrLf), tokeniser.line_ending());
+
+        let mut tokeniser = PushTokeniser::new(SymbolTable::default());
+        assert_eq!(1, tokeniser.feed(b"a \r").count());
+        assert_eq!(None, tokeniser.line_ending());
+        assert_eq!(3, tokeniser.feed(b"\nb\n").count());
+        assert_eq!(Some(LineEnding::CrLf), tokeniser.line_ending());
+    }
+
+    #[test]
+    fn push_emits_complete_tokens() {
+        let mut tokeniser = PushTokeniser::new(SymbolTable::default());
+        assert_eq!(0, tokeniser.feed(b"hel").count());
+        let tokens = tokeniser.feed(b"lo wor").map(|fragment| fragment.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(vec![Token::Ident("hello".into())], tokens);
+        let tokens = tokeniser.feed(b"ld \"a ").map(|fragment| fragment.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(vec![Token::Ident("world".into())], tokens);
+        assert_eq!(0, tokeniser.feed(b"b\"\xc2").count());
+        assert_eq!(0, tokeniser.feed(b"\xb5").count());
+        let tokens = tokeniser.finish().map(|fragment| fragment.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(vec![Token::Text("a b".into()), Token::Ident("µ".into()), Token::Newline], tokens);
+    }
+
+    #[test]
+    fn push_emits_at_whitespace() {
+        let mut tokeniser = PushTokeniser::new(SymbolTable::default());
+        assert_eq!(0, tokeniser.feed(b"(").count());
+        assert_eq!(0, tokeniser.feed(b"a)").count());
+        let tokens = tokeniser.feed(b" ").map(|fragment| fragment.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(vec![Token::Left(Paren), Token::Ident("a".into()), Token::Right(Paren)], tokens);
+        assert_eq!(0, tokeniser.feed(b"[").count());
+        let tokens = tokeniser.finish().map(|fragment| fragment.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(vec![Token::Left(Bracket), Token::Newline], tokens);
+    }
+
+    #[test]
+    fn push_error_terminates() {
+        let mut tokeniser = PushTokeniser::new(SymbolTable::default());
+        let fragments = tokeniser.feed(b"a \\ b ").collect::<Vec<_>>();
+        assert_eq!(2, fragments.len());
+        assert!(fragments[1].is_err());
+        assert_eq!(0, tokeniser.feed(b"c ").count());
+        assert_eq!(0, tokeniser.finish().count());
+    }
+
+    #[test]
+    fn push_recovers() {
+        let options = Options {
+            recover: true,
+            ..Options::default()
+        };
+        let fragments = push_in_chunks("a \\ b \"c\\q d\" e", options, 2);
+        assert_eq!(
+            vec![
+                Ok(Token::Ident("a".into())),
+                Err("unexpected character '\\' at line 1, column 3".into()),
+                Ok(Token::Ident("b".into())),
+                Err("unknown escape sequence \"q\" at line 1, column 10".into()),
+                Ok(Token::Ident("e".into())),
+                Ok(Token::Newline),
+            ],
+            fragments.into_iter().map(|fragment| fragment.map(|(token, _)| token)).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,21 +1,34 @@
 use crate::lexer::tests::Ownership::{Borrowed, Owned, NA};
-use crate::lexer::{Error, Tokeniser};
-use crate::metadata::Metadata;
-use crate::symbols::SymbolTable;
-use crate::token::ListDelimiter::{Brace, Bracket};
+use crate::lexer::{Comments, Error, Escape, Escapes, Options, Tokeniser};
+use crate::metadata::{ColumnUnit, Metadata};
+use crate::symbols::{SymbolString, SymbolTable};
+use crate::token::ListDelimiter::{Angle, Brace, Bracket};
 use crate::token::Token::{
-    Boolean, Character, Decimal, ExtendedSymbol, Ident, Left, Right, Symbol,
+    BigDecimal, BigInteger, Boolean, Byte, Bytes, Character, Comment, Decimal, EmbedBegin, EmbedEnd, ExtendedSymbol, Ident,
+    InterpolationBegin, InterpolationEnd, Left, RadixInteger, Right, Scientific, Signed, Symbol, TextSegment,
 };
-use crate::token::{Ascii, AsciiSlice, ListDelimiter, Token};
+use crate::token::{Ascii, AsciiSlice, LineEnding, ListDelimiter, Token};
+use crate::token::Radix::{Binary, Hexadecimal, Octal};
+use crate::token::Sign::{Minus, Plus};
 use std::borrow::Cow;
+use std::num::NonZeroU32;
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
 use ListDelimiter::Paren;
 use Token::{Integer, Newline, Text};
 use crate::token;
 
-fn tok_ok(str: &str) -> (Vec<Token>, Vec<Metadata>) {
-    let tok_with_metadata = Tokeniser::new(str, SymbolTable::default())
+fn tok_ok(str: &str) -> (Vec<Token<'_>>, Vec<Metadata>) {
+    tok_ok_with_options(str, Options::default())
+}
+
+fn tok_ok_with_options(str: &str, options: Options) -> (Vec<Token<'_>>, Vec<Metadata>) {
+    let tok_with_metadata = Tokeniser::with_options(str, SymbolTable::default(), options.clone())
         .map(Result::unwrap)
         .collect::<Vec<_>>();
+    for (_, metadata) in &tok_with_metadata {
+        assert_offsets(str, metadata, &options);
+    }
     let tokens = tok_with_metadata
         .iter()
         .cloned()
@@ -28,8 +41,75 @@ fn tok_ok(str: &str) -> (Vec<Token>, Vec<Metadata>) {
     (tokens, metadata)
 }
 
+/// Asserts that the byte offsets of the metadata agree with its lines and columns, where column 0
+/// denotes the line break ending the previous line. Columns are counted independently of the
+/// tokeniser, in the unit and with the tab stops of the given options. A line terminator counts
+/// as a single column, whatever its length.
+fn assert_offsets(str: &str, metadata: &Metadata, options: &Options) {
+    let (Some(start), Some(end)) = (&metadata.start, &metadata.end) else {
+        panic!("unbounded metadata {metadata:?}")
+    };
+    #[cfg(feature = "graphemes")]
+    let clusters: Vec<_> = str.grapheme_indices(true).map(|(offset, _)| offset).collect();
+    let mut chars = vec![];
+    let (mut line, mut column) = (1, 0);
+    let mut char_indices = str.char_indices().chain([(str.len(), '\n')]).peekable();
+    while let Some((offset, char)) = char_indices.next() {
+        let first = column + 1;
+        let terminator = matches!(char, '\n' | '\r' | '\u{85}' | '\u{2028}' | '\u{2029}');
+        column += match options.column_unit {
+            _ if terminator => 1,
+            ColumnUnit::Bytes => char.len_utf8() as u32,
+            ColumnUnit::Chars => 1,
+            ColumnUnit::Utf16 => char.len_utf16() as u32,
+            #[cfg(feature = "graphemes")]
+            ColumnUnit::Graphemes => u32::from(offset == str.len() || clusters.contains(&offset)),
+        };
+        if char == '\t' && let Some(tab_width) = options.tab_width {
+            column = column.div_ceil(tab_width.get()) * tab_width.get();
+        }
+        let mut end = (offset + char.len_utf8()).min(str.len());
+        if char == '\r' && let Some(&(next, '\n')) = char_indices.peek() && next < str.len() {
+            char_indices.next();
+            end += 1;
+        }
+        chars.push((line, first, column, offset, end));
+        if terminator {
+            line += 1;
+            column = 0;
+            chars.push((line, column, column, offset, end));
+        }
+    }
+    let start_offset = chars.iter()
+        .find(|&&(l, first, last, _, _)| (l, first) == (start.line, start.column) && first <= last)
+        .map(|&(_, _, _, offset, _)| offset);
+    let end_offset = chars.iter()
+        .rfind(|&&(l, _, last, _, _)| (l, last) == (end.line, end.column))
+        .map(|&(_, _, _, _, offset)| offset);
+    assert_eq!(Some(start.offset), start_offset, "start of {metadata:?}");
+    assert_eq!(Some(end.offset), end_offset, "end of {metadata:?}");
+}
+
+fn tok_recover(str: &str, options: Options) -> Vec<Result<(Token<'_>, Metadata), String>> {
+    let options = Options {
+        recover: true,
+        ..options
+    };
+    let fragments: Vec<_> = Tokeniser::with_options(str, SymbolTable::default(), options.clone())
+        .map(|fragment| fragment.map_err(|err| err.to_string()))
+        .collect();
+    for (_, metadata) in fragments.iter().flatten() {
+        assert_offsets(str, metadata, &options);
+    }
+    fragments
+}
+
 fn tok_err(str: &str) -> Box<Error> {
-    Tokeniser::new(str, SymbolTable::default())
+    tok_err_with_options(str, Options::default())
+}
+
+fn tok_err_with_options(str: &str, options: Options) -> Box<Error> {
+    Tokeniser::with_options(str, SymbolTable::default(), options)
         .map(Result::err)
         .skip_while(Option::is_none)
         .map(Option::unwrap)
@@ -48,13 +128,20 @@ fn is_owned(tokens: Vec<Token>) -> Vec<Ownership> {
     tokens
         .iter()
         .map(|token| match token {
-            Text(str) | Ident(str) => {
+            Text(str) | TextSegment(str) | Ident(str) | Comment(str) | BigInteger(token::BigInteger(str, _)) => {
                 if matches!(str, Cow::Owned(_)) {
                     Owned
                 } else {
                     Borrowed
                 }
             }
+            Bytes(bytes) => {
+                if matches!(bytes, Cow::Owned(_)) {
+                    Owned
+                } else {
+                    Borrowed
+                }
+            }
             _ => NA,
         })
         .collect()
@@ -85,10 +172,10 @@ fn text_unescaped() {
     assert_eq!(vec![Borrowed, NA, Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 13),
-            Metadata::bounds(1, 14, 2, 0),
-            Metadata::bounds(2, 1, 2, 4),
-            Metadata::bounds(2, 5, 3, 0)
+            Metadata::bounds(1, 1, 0, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 2, 0, 14),
+            Metadata::bounds(2, 1, 14, 2, 4, 18),
+            Metadata::bounds(2, 5, 18, 3, 0, 18)
         ],
         metadata
     );
@@ -111,10 +198,10 @@ fn text_unescaped_with_utf8() {
     assert_eq!(vec![Borrowed, NA, Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 17),
-            Metadata::bounds(1, 18, 2, 0),
-            Metadata::bounds(2, 1, 2, 7),
-            Metadata::bounds(2, 8, 3, 0)
+            Metadata::bounds(1, 1, 0, 1, 17, 23),
+            Metadata::bounds(1, 18, 23, 2, 0, 24),
+            Metadata::bounds(2, 1, 24, 2, 7, 37),
+            Metadata::bounds(2, 8, 37, 3, 0, 37)
         ],
         metadata
     );
@@ -128,8 +215,8 @@ fn text_escaped_newline() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 9),
-            Metadata::bounds(1, 10, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 2, 0, 9),
         ],
         metadata
     );
@@ -143,8 +230,8 @@ fn text_escaped_nul() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 9),
-            Metadata::bounds(1, 10, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 2, 0, 9),
         ],
         metadata
     );
@@ -158,8 +245,8 @@ fn text_escaped_hex() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 11),
-            Metadata::bounds(1, 12, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 11, 11),
+            Metadata::bounds(1, 12, 11, 2, 0, 11),
         ],
         metadata
     );
@@ -173,13 +260,43 @@ fn text_escaped_unicode_fixed() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 13),
-            Metadata::bounds(1, 14, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 2, 0, 13),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn text_escaped_surrogate_pair() {
+    let str = r#""a\uD83D\uDE00b\ud83d\ude00" '\uD83D\uDE00'"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(vec![Text("a😀b😀".into()), Character('😀'), Newline], tokens);
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 28, 28),
+            Metadata::bounds(1, 30, 29, 1, 43, 43),
+            Metadata::bounds(1, 44, 43, 2, 0, 43),
         ],
         metadata
     );
 }
 
+#[test]
+fn text_escaped_unpaired_surrogate_err() {
+    for (str, expect) in [
+        (r#""\uD83D""#, "unpaired surrogate \"D83D\" at line 1, column 7"),
+        (r#""\uD83Dx""#, "unpaired surrogate \"D83D\" at line 1, column 7"),
+        (r#""\uD83DA""#, "unpaired surrogate \"D83D\" at line 1, column 7"),
+        (r#""\uD83D\uD83D""#, "unpaired surrogate \"D83D\" at line 1, column 7"),
+        (r#""\uD83D\uDE0""#, "unpaired surrogate \"D83D\" at line 1, column 7"),
+        (r#""\uDE00""#, "unpaired surrogate \"DE00\" at line 1, column 7"),
+        (r#""\u{D83D}""#, "invalid codepoint \"D83D\" (codepoint out of range) at line 1, column 9"),
+    ] {
+        assert_eq!(expect, tok_err(str).to_string(), "for input {str}");
+    }
+}
+
 #[test]
 fn text_escaped_unicode_fixed_ascii() {
     let str = r#""hel\u007elo""#;
@@ -188,8 +305,8 @@ fn text_escaped_unicode_fixed_ascii() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 13),
-            Metadata::bounds(1, 14, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 2, 0, 13),
         ],
         metadata
     );
@@ -203,8 +320,8 @@ fn text_escaped_unicode_variable_24() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 16),
-            Metadata::bounds(1, 17, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 16, 16),
+            Metadata::bounds(1, 17, 16, 2, 0, 16),
         ],
         metadata
     );
@@ -218,8 +335,8 @@ fn text_escaped_unicode_variable_16() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 15),
-            Metadata::bounds(1, 16, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 15, 15),
+            Metadata::bounds(1, 16, 15, 2, 0, 15),
         ],
         metadata
     );
@@ -233,8 +350,8 @@ fn text_escaped_unicode_variable_ascii() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 15),
-            Metadata::bounds(1, 16, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 15, 15),
+            Metadata::bounds(1, 16, 15, 2, 0, 15),
         ],
         metadata
     );
@@ -260,6 +377,16 @@ fn text_escaped_hex_unparsable_err() {
     );
 }
 
+#[test]
+fn text_escaped_unicode_with_blank_err() {
+    let str = r#""hel\u{1 2}lo""#;
+    let err = tok_err(str);
+    assert_eq!(
+        "invalid codepoint \"1 \" (invalid digit found in string) at line 1, column 9",
+        err.to_string()
+    );
+}
+
 #[test]
 fn text_escaped_newline_with_utf8() {
     let str = r#""hel\nµℝ💣""#;
@@ -268,8 +395,8 @@ fn text_escaped_newline_with_utf8() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 10),
-            Metadata::bounds(1, 11, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 10, 16),
+            Metadata::bounds(1, 11, 16, 2, 0, 16),
         ],
         metadata
     );
@@ -283,8 +410,8 @@ fn text_escaped_carriage_return() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 9),
-            Metadata::bounds(1, 10, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 2, 0, 9),
         ],
         metadata
     );
@@ -298,8 +425,8 @@ fn text_escaped_tab() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 9),
-            Metadata::bounds(1, 10, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 2, 0, 9),
         ],
         metadata
     );
@@ -313,8 +440,8 @@ fn text_escaped_quote() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 9),
-            Metadata::bounds(1, 10, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 2, 0, 9),
         ],
         metadata
     );
@@ -328,8 +455,8 @@ fn text_escaped_backslash() {
     assert_eq!(vec![Owned, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 9),
-            Metadata::bounds(1, 10, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 2, 0, 9),
         ],
         metadata
     );
@@ -363,6 +490,134 @@ fn text_unterminated_err() {
     assert_eq!("unterminated literal at line 1, column 7", err.to_string());
 }
 
+#[test]
+fn raw_text() {
+    let str = r####"r"C:\temp\n" r#"say "µ\" ok"# r##"a"#b"## r"""####;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Text(r"C:\temp\n".into()),
+            Text(r#"say "µ\" ok"#.into()),
+            Text(r##"a"#b"##.into()),
+            Text("".into()),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(vec![Borrowed, Borrowed, Borrowed, Borrowed, NA], is_owned(tokens));
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 12, 12),
+            Metadata::bounds(1, 14, 13, 1, 29, 30),
+            Metadata::bounds(1, 31, 31, 1, 41, 42),
+            Metadata::bounds(1, 43, 43, 1, 45, 46),
+            Metadata::bounds(1, 46, 46, 2, 0, 46),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn raw_text_not_opened() {
+    let str = r#"r r# rx"#;
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Ident("r".into()),
+            Ident("r".into()),
+            Symbol(Ascii(b'#')),
+            Ident("rx".into()),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn raw_text_unterminated_err() {
+    let str = r###"a r#"hello"
+"#"###;
+    let err = tok_err(str);
+    assert_eq!(r##"unterminated raw literal at line 1, column 3 (expected "#)"##, err.to_string());
+}
+
+#[test]
+fn text_block() {
+    let str = "x: \"\"\"\n    hello\n      µ world\n    \"\"\"\ny";
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Ident("x".into()),
+            Symbol(Ascii(b':')),
+            Text("hello\n  µ world".into()),
+            Newline,
+            Ident("y".into()),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(vec![Borrowed, NA, Owned, NA, Borrowed, NA], is_owned(tokens));
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 4, 3, 4, 7, 39),
+            Metadata::bounds(4, 8, 39, 5, 0, 40),
+            Metadata::bounds(5, 1, 40, 5, 1, 41),
+            Metadata::bounds(5, 2, 41, 6, 0, 41),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn text_block_unindented() {
+    let str = "\"\"\"\na\n\n  b \\n\n\"\"\"";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Text("a\n\n  b \\n".into()), Newline], tokens);
+    assert_eq!(vec![Borrowed, NA], is_owned(tokens));
+}
+
+#[test]
+fn text_block_indentation_retained() {
+    let str = "\"\"\"\n      a\n\n    b\n  \"\"\"";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Text("    a\n\n  b".into()), Newline], tokens);
+}
+
+#[test]
+fn text_block_mixed_indentation() {
+    let str = "\"\"\"\n\tfoo\n    bar\n    \"\"\"";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Text("\tfoo\n    bar".into()), Newline], tokens);
+
+    let str = "\"\"\"\n\t  foo\n\t\tbar\n\n\t\"\"\"";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Text("  foo\n\tbar\n".into()), Newline], tokens);
+}
+
+#[test]
+fn text_block_inline() {
+    let str = r#""""say "hi" now""" """""""#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(vec![Text(r#"say "hi" now"#.into()), Text("".into()), Newline], tokens);
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 18, 18),
+            Metadata::bounds(1, 20, 19, 1, 25, 25),
+            Metadata::bounds(1, 26, 25, 2, 0, 25),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn text_block_unterminated_err() {
+    let str = "a \"\"\"\nabc\n\"\"";
+    let err = tok_err(str);
+    assert_eq!("unterminated literal at line 1, column 3", err.to_string());
+}
+
 #[test]
 fn text_unknown_escape_err() {
     let str = r#""hello\s
@@ -374,6 +629,212 @@ fn text_unknown_escape_err() {
     );
 }
 
+#[test]
+fn text_escaped_extended() {
+    let str = r#""\a\b\f\v\e\/""#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(vec![Text("\x07\x08\x0C\x0B\x1B/".into()), Newline], tokens);
+    assert_eq!(vec![Owned, NA], is_owned(tokens));
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 14, 14),
+            Metadata::bounds(1, 15, 14, 2, 0, 14),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn text_line_continuation() {
+    let str = "\"hello \\\n     world\" x";
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(vec![Text("hello world".into()), Ident("x".into()), Newline], tokens);
+    assert_eq!(vec![Owned, Borrowed, NA], is_owned(tokens));
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 2, 11, 20),
+            Metadata::bounds(2, 13, 21, 2, 13, 22),
+            Metadata::bounds(2, 14, 22, 3, 0, 22),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn text_line_continuation_at_end_err() {
+    for (str, expect) in [
+        ("\"hello \\\n", "unterminated literal at line 2, column 0"),
+        ("\"hello \\", "unterminated literal at line 2, column 0"),
+    ] {
+        assert_eq!(expect, tok_err(str).to_string(), "for input {str}");
+    }
+}
+
+#[test]
+fn text_line_continuation_disabled_err() {
+    let str = "\"hello \\\n world\"";
+    let options = Options {
+        escapes: Escapes::mercury().without(Escape::LineContinuation),
+        ..Options::default()
+    };
+    let err = tok_err_with_options(str, options);
+    assert_eq!(
+        "unknown escape sequence \"\n\" at line 1, column 9",
+        err.to_string()
+    );
+}
+
+#[test]
+fn character_line_continuation() {
+    let str = "'\\\n  a'";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Character('a'), Newline], tokens);
+}
+
+#[test]
+fn text_json_escapes() {
+    let str = r#""\"\\\/\b\f\n\r\té""#;
+    let options = Options {
+        escapes: Escapes::json(),
+        ..Options::default()
+    };
+    let (tokens, _) = tok_ok_with_options(str, options);
+    assert_eq!(vec![Text("\"\\/\x08\x0C\n\r\té".into()), Newline], tokens);
+}
+
+#[test]
+fn text_json_escapes_err() {
+    for (str, expect) in [
+        (r#""\'""#, "unknown escape sequence \"'\" at line 1, column 3"),
+        (r#""\x41""#, "unknown escape sequence \"x\" at line 1, column 3"),
+        (r#""\u{41}""#, "unknown escape sequence \"u{\" at line 1, column 4"),
+        (r#""\0""#, "unknown escape sequence \"0\" at line 1, column 3"),
+        ("\"a\\\nb\"", "unknown escape sequence \"\n\" at line 1, column 4"),
+    ] {
+        let options = Options {
+            escapes: Escapes::json(),
+            ..Options::default()
+        };
+        assert_eq!(expect, tok_err_with_options(str, options).to_string(), "for input {str}");
+    }
+}
+
+#[test]
+fn text_custom_escapes() {
+    let options = Options {
+        escapes: Escapes::none().with(Escape::Backslash).with(Escape::UnicodeBraced),
+        ..Options::default()
+    };
+    let (tokens, _) = tok_ok_with_options(r#""\\\u{e9}""#, options.clone());
+    assert_eq!(vec![Text("\\é".into()), Newline], tokens);
+    let err = tok_err_with_options(r#""\u00e9""#, options.clone());
+    assert_eq!("unknown escape sequence \"u0\" at line 1, column 4", err.to_string());
+    let err = tok_err_with_options(r#""\n""#, options);
+    assert_eq!("unknown escape sequence \"n\" at line 1, column 3", err.to_string());
+}
+
+#[test]
+fn interpolation() {
+    let str = r#""Hello ${user.name}!" x"#;
+    let options = Options {
+        interpolation: true,
+        ..Options::default()
+    };
+    let (tokens, metadata) = tok_ok_with_options(str, options);
+    assert_eq!(
+        vec![
+            InterpolationBegin,
+            TextSegment("Hello ".into()),
+            EmbedBegin,
+            Ident("user".into()),
+            Symbol(token::Ascii(b'.')),
+            Ident("name".into()),
+            EmbedEnd,
+            TextSegment("!".into()),
+            InterpolationEnd,
+            Ident("x".into()),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(vec![NA, Borrowed, NA, Borrowed, NA, Borrowed, NA, Borrowed, NA, Borrowed, NA], is_owned(tokens));
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 1, 14, 14),
+            Metadata::bounds(1, 15, 14, 1, 18, 18),
+            Metadata::bounds(1, 19, 18, 1, 19, 19),
+            Metadata::bounds(1, 20, 19, 1, 20, 20),
+            Metadata::bounds(1, 21, 20, 1, 21, 21),
+            Metadata::bounds(1, 23, 22, 1, 23, 23),
+            Metadata::bounds(1, 24, 23, 2, 0, 23),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn interpolation_nested_and_escaped() {
+    let str = r#""a\${b} $c ${ {d} }${"e${-1}"}""#;
+    let options = Options {
+        interpolation: true,
+        signed_numbers: true,
+        ..Options::default()
+    };
+    let (tokens, _) = tok_ok_with_options(str, options);
+    assert_eq!(
+        vec![
+            InterpolationBegin,
+            TextSegment("a${b} $c ".into()),
+            EmbedBegin,
+            Left(Brace),
+            Ident("d".into()),
+            Right(Brace),
+            EmbedEnd,
+            EmbedBegin,
+            InterpolationBegin,
+            TextSegment("e".into()),
+            EmbedBegin,
+            Signed(Minus, Box::new(Integer(1))),
+            EmbedEnd,
+            InterpolationEnd,
+            EmbedEnd,
+            InterpolationEnd,
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn interpolation_without_embeds() {
+    let options = Options {
+        interpolation: true,
+        ..Options::default()
+    };
+    let (tokens, _) = tok_ok_with_options(r#""a $b {c}""#, options);
+    assert_eq!(vec![Text("a $b {c}".into()), Newline], tokens);
+    let (tokens, _) = tok_ok(r#""a ${b}""#);
+    assert_eq!(vec![Text("a ${b}".into()), Newline], tokens);
+}
+
+#[test]
+fn interpolation_unterminated_err() {
+    for (str, expect) in [
+        ("\"a ${b}\n", "unterminated literal at line 1, column 8"),
+        ("\"a ${b\n}\"", "unterminated literal at line 1, column 7"),
+    ] {
+        let options = Options {
+            interpolation: true,
+            ..Options::default()
+        };
+        assert_eq!(expect, tok_err_with_options(str, options).to_string(), "for input {str}");
+    }
+}
+
 #[test]
 fn character_unescaped() {
     let str = r#"  'a'
@@ -385,10 +846,10 @@ fn character_unescaped() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 3, 1, 5),
-            Metadata::bounds(1, 6, 2, 0),
-            Metadata::bounds(2, 1, 2, 3),
-            Metadata::bounds(2, 4, 3, 0),
+            Metadata::bounds(1, 3, 2, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 2, 0, 6),
+            Metadata::bounds(2, 1, 6, 2, 3, 9),
+            Metadata::bounds(2, 4, 9, 3, 0, 9),
         ],
         metadata
     );
@@ -405,10 +866,10 @@ fn character_unescaped_with_unicode() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 3),
-            Metadata::bounds(1, 4, 2, 0),
-            Metadata::bounds(2, 1, 2, 3),
-            Metadata::bounds(2, 4, 3, 0),
+            Metadata::bounds(1, 1, 0, 1, 3, 6),
+            Metadata::bounds(1, 4, 6, 2, 0, 7),
+            Metadata::bounds(2, 1, 7, 2, 3, 11),
+            Metadata::bounds(2, 4, 11, 3, 0, 11),
         ],
         metadata
     );
@@ -421,8 +882,8 @@ fn character_escaped_newline() {
     assert_eq!(vec![Character('\n'), Newline], tokens);
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 4),
-            Metadata::bounds(1, 5, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 2, 0, 4),
         ],
         metadata
     );
@@ -467,6 +928,61 @@ fn character_unknown_escape_err() {
     );
 }
 
+#[test]
+fn bytes_and_byte() {
+    let str = r#"b"abc" b"\x00\xffA\n" b'a' b'\x7f' b'\'' b'\e'"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Bytes(b"abc".into()),
+            Bytes(b"\x00\xffA\n".into()),
+            Byte(b'a'),
+            Byte(0x7f),
+            Byte(b'\''),
+            Byte(0x1b),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(vec![Borrowed, Owned, NA, NA, NA, NA, NA], is_owned(tokens));
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 6, 6),
+            Metadata::bounds(1, 8, 7, 1, 21, 21),
+            Metadata::bounds(1, 23, 22, 1, 26, 26),
+            Metadata::bounds(1, 28, 27, 1, 34, 34),
+            Metadata::bounds(1, 36, 35, 1, 40, 40),
+            Metadata::bounds(1, 42, 41, 1, 46, 46),
+            Metadata::bounds(1, 47, 46, 2, 0, 46),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn bytes_not_opened() {
+    let str = "b bx";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Ident("b".into()), Ident("bx".into()), Newline], tokens);
+}
+
+#[test]
+fn bytes_errs() {
+    for (str, expect) in [
+        (r#"b"µ""#, "unexpected character 'µ' at line 1, column 3"),
+        (r#"b"\xg0""#, "unknown escape sequence \"xg\" at line 1, column 5"),
+        (r#"b"\x0""#, "unknown escape sequence \"x0\"\" at line 1, column 6"),
+        (r#"b"\u{41}""#, "unknown escape sequence \"u\" at line 1, column 4"),
+        ("b\"\\\n\"", "unknown escape sequence \"\n\" at line 1, column 4"),
+        (r#"b"abc"#, "unterminated literal at line 1, column 6"),
+        ("b''", "empty character literal at line 1, column 3"),
+        ("b'ab'", "unexpected character 'b' at line 1, column 4"),
+        ("b'µ'", "unexpected character 'µ' at line 1, column 3"),
+    ] {
+        assert_eq!(expect, tok_err(str).to_string(), "for input {str}");
+    }
+}
+
 #[test]
 fn escape_during_whitespace_err() {
     let str = r#"\n
@@ -494,11 +1010,11 @@ fn left_and_right_paren() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 1, 5),
-            Metadata::bounds(1, 6, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 2, 0, 5),
         ],
         metadata
     );
@@ -522,12 +1038,12 @@ fn left_and_right_paren_around_text() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 11),
-            Metadata::bounds(1, 12, 2, 0),
-            Metadata::bounds(2, 1, 2, 16),
-            Metadata::bounds(2, 17, 2, 17),
-            Metadata::bounds(2, 18, 3, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 11, 11),
+            Metadata::bounds(1, 12, 11, 2, 0, 12),
+            Metadata::bounds(2, 1, 12, 2, 16, 28),
+            Metadata::bounds(2, 17, 28, 2, 17, 29),
+            Metadata::bounds(2, 18, 29, 3, 0, 29),
         ],
         metadata
     );
@@ -549,11 +1065,11 @@ fn left_and_right_brace() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 1, 5),
-            Metadata::bounds(1, 6, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 2, 0, 5),
         ],
         metadata
     );
@@ -566,7 +1082,7 @@ fn dash() {
     assert_eq!(
         vec![
             Symbol(Ascii(b'-')),
-            ExtendedSymbol(AsciiSlice(&[b'-', b'-'])),
+            ExtendedSymbol(AsciiSlice(b"--".into())),
             Symbol(Ascii(b'-')),
             Newline
         ],
@@ -574,10 +1090,10 @@ fn dash() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 4, 1, 5),
-            Metadata::bounds(1, 7, 1, 7),
-            Metadata::bounds(1, 8, 2, 0),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 4, 3, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 2, 0, 7),
         ],
         metadata
     );
@@ -590,7 +1106,7 @@ fn colon() {
     assert_eq!(
         vec![
             Symbol(Ascii(b':')),
-            ExtendedSymbol(AsciiSlice(&[b':', b':'])),
+            ExtendedSymbol(AsciiSlice(b"::".into())),
             Symbol(Ascii(b':')),
             Newline
         ],
@@ -598,10 +1114,10 @@ fn colon() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 4, 1, 5),
-            Metadata::bounds(1, 7, 1, 7),
-            Metadata::bounds(1, 8, 2, 0),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 4, 3, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 2, 0, 7),
         ],
         metadata
     );
@@ -623,11 +1139,11 @@ fn comma() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 1, 5),
-            Metadata::bounds(1, 7, 1, 7),
-            Metadata::bounds(1, 8, 2, 0),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 2, 0, 7),
         ],
         metadata
     );
@@ -640,8 +1156,8 @@ fn integer_newline_terminated() {
     assert_eq!(vec![Integer(1234567890), Newline], tokens);
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 10),
-            Metadata::bounds(1, 11, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 10, 10),
+            Metadata::bounds(1, 11, 10, 2, 0, 10),
         ],
         metadata
     );
@@ -654,8 +1170,8 @@ fn integer_zero_newline_terminated() {
     assert_eq!(vec![Integer(0), Newline], tokens);
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 2, 0, 1),
         ],
         metadata
     );
@@ -671,9 +1187,9 @@ fn integer_colon_terminated() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 13),
-            Metadata::bounds(1, 14, 1, 14),
-            Metadata::bounds(1, 15, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 1, 14, 14),
+            Metadata::bounds(1, 15, 14, 2, 0, 14),
         ],
         metadata
     );
@@ -689,10 +1205,10 @@ fn integer_dash_terminated() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 3),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 1, 7),
-            Metadata::bounds(1, 8, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 3, 3),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 2, 0, 7),
         ],
         metadata
     );
@@ -708,21 +1224,53 @@ fn integer_comma_terminated() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 3),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 1, 7),
-            Metadata::bounds(1, 8, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 3, 3),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 2, 0, 7),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn big_integer() {
+    let str = r#"1234567890123456789012345678901234567890: 340_282_366_920_938_463_463_374_607_431_768_211_456"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            BigInteger(token::BigInteger("1234567890123456789012345678901234567890".into(), None)),
+            Symbol(Ascii(b':')),
+            BigInteger(token::BigInteger("340282366920938463463374607431768211456".into(), None)),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 40, 40),
+            Metadata::bounds(1, 41, 40, 1, 41, 41),
+            Metadata::bounds(1, 43, 42, 1, 93, 93),
+            Metadata::bounds(1, 94, 93, 2, 0, 93),
         ],
         metadata
     );
+    assert_eq!(vec![Borrowed, NA, Owned, NA], is_owned(tokens));
+}
+
+#[test]
+fn integer_max() {
+    let str = "340282366920938463463374607431768211455";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Integer(u128::MAX), Newline], tokens);
 }
 
 #[test]
-fn integer_too_large_err() {
-    let str = r#"1234567890123456789012345678901234567890:"#;
+fn big_integer_invalid_err() {
+    let str = r#"1234567890123456789012345678901234567890k:"#;
     let err = tok_err(str);
     assert_eq!(
-        "unparsable integer 1234567890123456789012345678901234567890 (number too large to fit in target type) at line 1, column 41",
+        "unparsable integer 1234567890123456789012345678901234567890k (invalid digit found in string) at line 1, column 42",
         err.to_string()
     );
 }
@@ -748,91 +1296,187 @@ fn integer_invalid_due_to_utf8_err() {
 }
 
 #[test]
-fn decimal_newline_terminated() {
-    let str = r#"1234567890.0123456789"#;
+fn radix_integers() {
+    let str = "0xff 0XAb_Cd 0o755 0b1010_0101 0b0";
     let (tokens, metadata) = tok_ok(str);
-    assert_eq!(vec![Decimal(token::Decimal(1234567890, 123456789, 10)), Newline], tokens);
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 21),
-            Metadata::bounds(1, 22, 2, 0),
+            RadixInteger(0xff, Hexadecimal),
+            RadixInteger(0xabcd, Hexadecimal),
+            RadixInteger(0o755, Octal),
+            RadixInteger(0b1010_0101, Binary),
+            RadixInteger(0, Binary),
+            Newline
         ],
-        metadata
+        tokens
     );
-}
-
-#[test]
-fn decimal_small() {
-    let str = r#"1234567890.0001"#;
-    let (tokens, metadata) = tok_ok(str);
-    assert_eq!(vec![Decimal(token::Decimal(1234567890, 1, 4)), Newline], tokens);
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 15),
-            Metadata::bounds(1, 16, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 4, 4),
+            Metadata::bounds(1, 6, 5, 1, 12, 12),
+            Metadata::bounds(1, 14, 13, 1, 18, 18),
+            Metadata::bounds(1, 20, 19, 1, 30, 30),
+            Metadata::bounds(1, 32, 31, 1, 34, 34),
+            Metadata::bounds(1, 35, 34, 2, 0, 34),
         ],
         metadata
     );
 }
 
 #[test]
-fn decimal_implied_leading_zero() {
-    let str = r#".123"#;
-    let (tokens, metadata) = tok_ok(str);
-    assert_eq!(vec![Decimal(token::Decimal(0, 123, 3)), Newline], tokens);
+fn radix_integer_symbol_terminated() {
+    let str = "0x1f:0b1,";
+    let (tokens, _) = tok_ok(str);
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 4),
-            Metadata::bounds(1, 5, 2, 0),
+            RadixInteger(0x1f, Hexadecimal),
+            Symbol(Ascii(b':')),
+            RadixInteger(1, Binary),
+            Symbol(Ascii(b',')),
+            Newline
         ],
-        metadata
+        tokens
     );
 }
 
 #[test]
-fn symbol_and_decimal() {
-    let str = r#". .123"#;
-    let (tokens, metadata) = tok_ok(str);
+fn radix_integer_invalid_digit_err() {
+    let str = "0b102";
+    let err = tok_err(str);
     assert_eq!(
-        vec![Symbol(Ascii(b'.')), Decimal(token::Decimal(0, 123, 3)), Newline],
-        tokens
+        "unparsable integer 0b102 (invalid digit found in string) at line 1, column 6",
+        err.to_string()
     );
+}
+
+#[test]
+fn radix_integer_empty_err() {
+    let str = "0x ";
+    let err = tok_err(str);
     assert_eq!(
-        vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 3, 1, 6),
-            Metadata::bounds(1, 7, 2, 0),
-        ],
-        metadata
+        "unparsable integer 0x (cannot parse integer from empty string) at line 1, column 3",
+        err.to_string()
     );
 }
 
 #[test]
-fn decimal_colon_terminated() {
-    let str = r#"1_234_567_890.0_123_456_789:"#;
+fn big_radix_integer() {
+    let str = "0x1_0000_0000_0000_0000_0000_0000_0000_0000 0b1";
     let (tokens, metadata) = tok_ok(str);
     assert_eq!(
         vec![
-            Decimal(token::Decimal(1234567890, 123456789, 10)),
-            Symbol(Ascii(b':')),
+            BigInteger(token::BigInteger("100000000000000000000000000000000".into(), Some(Hexadecimal))),
+            RadixInteger(1, Binary),
             Newline
         ],
         tokens
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 27),
-            Metadata::bounds(1, 28, 1, 28),
-            Metadata::bounds(1, 29, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 43, 43),
+            Metadata::bounds(1, 45, 44, 1, 47, 47),
+            Metadata::bounds(1, 48, 47, 2, 0, 47),
         ],
         metadata
     );
 }
 
 #[test]
-fn decimal_comma_terminated() {
-    let str = r#"1_234_567_890.0_123_456_789,12.34"#;
-    let (tokens, metadata) = tok_ok(str);
+fn big_radix_integer_invalid_digit_err() {
+    let str = "0b1111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111112";
+    let err = tok_err(str);
+    assert_eq!(
+        format!("unparsable integer {str} (invalid digit found in string) at line 1, column 133"),
+        err.to_string()
+    );
+}
+
+#[test]
+fn decimal_newline_terminated() {
+    let str = r#"1234567890.0123456789"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(vec![Decimal(token::Decimal(1234567890, 123456789, 10)), Newline], tokens);
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 21, 21),
+            Metadata::bounds(1, 22, 21, 2, 0, 21),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn decimal_small() {
+    let str = r#"1234567890.0001"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(vec![Decimal(token::Decimal(1234567890, 1, 4)), Newline], tokens);
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 15, 15),
+            Metadata::bounds(1, 16, 15, 2, 0, 15),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn decimal_implied_leading_zero() {
+    let str = r#".123"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(vec![Decimal(token::Decimal(0, 123, 3)), Newline], tokens);
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 2, 0, 4),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn symbol_and_decimal() {
+    let str = r#". .123"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![Symbol(Ascii(b'.')), Decimal(token::Decimal(0, 123, 3)), Newline],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 3, 2, 1, 6, 6),
+            Metadata::bounds(1, 7, 6, 2, 0, 6),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn decimal_colon_terminated() {
+    let str = r#"1_234_567_890.0_123_456_789:"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Decimal(token::Decimal(1234567890, 123456789, 10)),
+            Symbol(Ascii(b':')),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 27, 27),
+            Metadata::bounds(1, 28, 27, 1, 28, 28),
+            Metadata::bounds(1, 29, 28, 2, 0, 28),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn decimal_comma_terminated() {
+    let str = r#"1_234_567_890.0_123_456_789,12.34"#;
+    let (tokens, metadata) = tok_ok(str);
     assert_eq!(
         vec![
             Decimal(token::Decimal(1234567890, 123456789, 10)),
@@ -844,31 +1488,75 @@ fn decimal_comma_terminated() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 27),
-            Metadata::bounds(1, 28, 1, 28),
-            Metadata::bounds(1, 29, 1, 33),
-            Metadata::bounds(1, 34, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 27, 27),
+            Metadata::bounds(1, 28, 27, 1, 28, 28),
+            Metadata::bounds(1, 29, 28, 1, 33, 33),
+            Metadata::bounds(1, 34, 33, 2, 0, 33),
         ],
         metadata
     );
 }
 
 #[test]
-fn decimal_whole_too_large_err() {
+fn big_decimal_whole() {
+    let str = r#"1234567890123456789012345678901234567890.5:"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            BigDecimal(token::BigDecimal("1234567890123456789012345678901234567890".into(), "5".into())),
+            Symbol(Ascii(b':')),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 42, 42),
+            Metadata::bounds(1, 43, 42, 1, 43, 43),
+            Metadata::bounds(1, 44, 43, 2, 0, 43),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn big_decimal_fractional() {
+    let str = r#"1_234_567_890.1234567890123456789012345678901234567890 .1234567890123456789012345678901234567890"#;
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            BigDecimal(token::BigDecimal("1234567890".into(), "1234567890123456789012345678901234567890".into())),
+            BigDecimal(token::BigDecimal("0".into(), "1234567890123456789012345678901234567890".into())),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 54, 54),
+            Metadata::bounds(1, 56, 55, 1, 96, 96),
+            Metadata::bounds(1, 97, 96, 2, 0, 96),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn decimal_whole_too_large_and_fractional_empty_err() {
     let str = r#"1234567890123456789012345678901234567890.:"#;
     let err = tok_err(str);
     assert_eq!(
-        "unparsable integer 1234567890123456789012345678901234567890 (number too large to fit in target type) at line 1, column 41",
+        "unparsable decimal 1234567890123456789012345678901234567890. (cannot parse integer from empty string) at line 1, column 42",
         err.to_string()
     );
 }
 
 #[test]
-fn decimal_fractional_too_large_err() {
-    let str = r#"1234567890.1234567890123456789012345678901234567890:"#;
+fn big_decimal_fractional_invalid_err() {
+    let str = r#"1.1234567890123456789012345678901234567890k:"#;
     let err = tok_err(str);
     assert_eq!(
-        "unparsable decimal 1234567890.1234567890123456789012345678901234567890 (number too large to fit in target type) at line 1, column 52",
+        "unparsable decimal 1.1234567890123456789012345678901234567890k (invalid digit found in string) at line 1, column 44",
         err.to_string()
     );
 }
@@ -903,6 +1591,131 @@ fn decimal_fractional_invalid_due_to_utf8_err() {
     );
 }
 
+#[test]
+fn scientific() {
+    let str = "1e10 1.5E-3 .5e+2 1_0e1_0 0e0";
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Scientific(token::Scientific(token::Decimal(1, 0, 0), 10)),
+            Scientific(token::Scientific(token::Decimal(1, 5, 1), -3)),
+            Scientific(token::Scientific(token::Decimal(0, 5, 1), 2)),
+            Scientific(token::Scientific(token::Decimal(10, 0, 0), 10)),
+            Scientific(token::Scientific(token::Decimal(0, 0, 0), 0)),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 4, 4),
+            Metadata::bounds(1, 6, 5, 1, 11, 11),
+            Metadata::bounds(1, 13, 12, 1, 17, 17),
+            Metadata::bounds(1, 19, 18, 1, 25, 25),
+            Metadata::bounds(1, 27, 26, 1, 29, 29),
+            Metadata::bounds(1, 30, 29, 2, 0, 29),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn scientific_symbol_terminated() {
+    let str = "[1e-3,2.5e2]";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Left(Bracket),
+            Scientific(token::Scientific(token::Decimal(1, 0, 0), -3)),
+            Symbol(Ascii(b',')),
+            Scientific(token::Scientific(token::Decimal(2, 5, 1), 2)),
+            Right(Bracket),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn scientific_empty_exponent_err() {
+    let str = "1.5e";
+    let err = tok_err(str);
+    assert_eq!(
+        "unparsable exponent  (cannot parse integer from empty string) at line 1, column 5",
+        err.to_string()
+    );
+}
+
+#[test]
+fn scientific_sign_only_exponent_err() {
+    let str = "1e- 2";
+    let err = tok_err(str);
+    assert_eq!(
+        "unparsable exponent - (invalid digit found in string) at line 1, column 4",
+        err.to_string()
+    );
+}
+
+#[test]
+fn scientific_invalid_exponent_err() {
+    let str = "1e5x";
+    let err = tok_err(str);
+    assert_eq!(
+        "unparsable exponent 5x (invalid digit found in string) at line 1, column 5",
+        err.to_string()
+    );
+}
+
+#[test]
+fn scientific_big_significand() {
+    let str = "123456789012345678901234567890123456789012e5 1.1234567890123456789012345678901234567890e-2 1_234567890123456789012345678901234567890_12e-40 1234567890123456789012345678901234567890e-38";
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            BigDecimal(token::BigDecimal("12345678901234567890123456789012345678901200000".into(), "0".into())),
+            BigDecimal(token::BigDecimal("0".into(), "011234567890123456789012345678901234567890".into())),
+            BigDecimal(token::BigDecimal("12".into(), "3456789012345678901234567890123456789012".into())),
+            Decimal(token::Decimal(12, 34567890123456789012345678901234567890, 38)),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 44, 44),
+            Metadata::bounds(1, 46, 45, 1, 90, 90),
+            Metadata::bounds(1, 92, 91, 1, 139, 139),
+            Metadata::bounds(1, 141, 140, 1, 184, 184),
+            Metadata::bounds(1, 185, 184, 2, 0, 184),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn scientific_exponent_at_limit() {
+    let str = "1e65536 1234567890123456789012345678901234567890e-65536";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(Scientific(token::Scientific(token::Decimal(1, 0, 0), 65536)), tokens[0]);
+    let BigDecimal(token::BigDecimal(whole, fractional)) = &tokens[1] else {
+        panic!("expected a big decimal, got {:?}", tokens[1]);
+    };
+    assert_eq!(("0", 65536), (whole.as_ref(), fractional.len()));
+}
+
+#[test]
+fn scientific_exponent_too_large_err() {
+    for (str, expect) in [
+        ("1e65537", "exponent 65537 too large at line 1, column 8"),
+        ("1.5e-65537", "exponent -65537 too large at line 1, column 11"),
+        ("1234567890123456789012345678901234567890e65537", "exponent 65537 too large at line 1, column 47"),
+        ("1e9999999999", "exponent 9999999999 too large at line 1, column 13"),
+    ] {
+        let err = tok_err(str);
+        assert_eq!(expect, err.to_string(), "for {str:?}");
+    }
+}
+
 #[test]
 fn ident() {
     let str = r#"first second
@@ -921,11 +1734,11 @@ third"#;
     assert_eq!(vec![Borrowed, Borrowed, NA, Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 5),
-            Metadata::bounds(1, 7, 1, 12),
-            Metadata::bounds(1, 13, 2, 0),
-            Metadata::bounds(2, 1, 2, 5),
-            Metadata::bounds(2, 6, 3, 0),
+            Metadata::bounds(1, 1, 0, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 12, 12),
+            Metadata::bounds(1, 13, 12, 2, 0, 13),
+            Metadata::bounds(2, 1, 13, 2, 5, 18),
+            Metadata::bounds(2, 6, 18, 3, 0, 18),
         ],
         metadata
     );
@@ -939,8 +1752,8 @@ fn ident_with_mid_and_trailing_digits() {
     assert_eq!(vec![Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 15),
-            Metadata::bounds(1, 16, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 15, 15),
+            Metadata::bounds(1, 16, 15, 2, 0, 15),
         ],
         metadata
     );
@@ -954,8 +1767,8 @@ fn ident_with_underscores() {
     assert_eq!(vec![Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 13),
-            Metadata::bounds(1, 14, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 2, 0, 13),
         ],
         metadata
     );
@@ -979,11 +1792,11 @@ third"#;
     assert_eq!(vec![Borrowed, Borrowed, NA, Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 5),
-            Metadata::bounds(1, 7, 1, 15),
-            Metadata::bounds(1, 16, 2, 0),
-            Metadata::bounds(2, 1, 2, 5),
-            Metadata::bounds(2, 6, 3, 0),
+            Metadata::bounds(1, 1, 0, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 15, 21),
+            Metadata::bounds(1, 16, 21, 2, 0, 22),
+            Metadata::bounds(2, 1, 22, 2, 5, 27),
+            Metadata::bounds(2, 6, 27, 3, 0, 27),
         ],
         metadata
     );
@@ -1007,16 +1820,216 @@ third"#;
     assert_eq!(vec![Borrowed, Borrowed, NA, Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 5),
-            Metadata::bounds(1, 7, 1, 16),
-            Metadata::bounds(1, 17, 2, 0),
-            Metadata::bounds(2, 1, 2, 5),
-            Metadata::bounds(2, 6, 3, 0),
+            Metadata::bounds(1, 1, 0, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 16, 22),
+            Metadata::bounds(1, 17, 22, 2, 0, 23),
+            Metadata::bounds(2, 1, 23, 2, 5, 28),
+            Metadata::bounds(2, 6, 28, 3, 0, 28),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn column_units() {
+    let str = "a µℝ💣 e\u{301} b";
+    for (column_unit, expect) in [
+        (ColumnUnit::Bytes, [(1, 1), (3, 11), (13, 15), (17, 17), (18, 0)]),
+        (ColumnUnit::Chars, [(1, 1), (3, 5), (7, 8), (10, 10), (11, 0)]),
+        (ColumnUnit::Utf16, [(1, 1), (3, 6), (8, 9), (11, 11), (12, 0)]),
+        #[cfg(feature = "graphemes")]
+        (ColumnUnit::Graphemes, [(1, 1), (3, 5), (7, 7), (9, 9), (10, 0)]),
+    ] {
+        let (tokens, metadata) = tok_ok_with_options(str, Options {
+            column_unit,
+            ..Options::default()
+        });
+        assert_eq!(
+            vec![
+                Ident("a".into()),
+                Ident("µℝ💣".into()),
+                Ident("e\u{301}".into()),
+                Ident("b".into()),
+                Newline
+            ],
+            tokens
+        );
+        let offsets = [(0, 1), (2, 11), (12, 15), (16, 17), (17, 17)];
+        let expect = expect.iter().zip(offsets).enumerate().map(|(index, (&(start_column, end_column), (start_offset, end_offset)))| {
+            let end_line = if index == 4 { 2 } else { 1 };
+            Metadata::bounds(1, start_column, start_offset, end_line, end_column, end_offset)
+        }).collect::<Vec<_>>();
+        assert_eq!(expect, metadata, "for {column_unit:?}");
+    }
+}
+
+#[test]
+fn tab_stops() {
+    let str = "\ta  b\t1\tc\n  \t\"d\te\"\n";
+    for (tab_width, expect) in [
+        (None, [(1, 2, 1, 2), (1, 5, 1, 5), (1, 7, 1, 7), (1, 9, 1, 9), (1, 10, 2, 0), (2, 4, 2, 8), (2, 9, 3, 0)]),
+        (NonZeroU32::new(1), [(1, 2, 1, 2), (1, 5, 1, 5), (1, 7, 1, 7), (1, 9, 1, 9), (1, 10, 2, 0), (2, 4, 2, 8), (2, 9, 3, 0)]),
+        (NonZeroU32::new(4), [(1, 5, 1, 5), (1, 8, 1, 8), (1, 13, 1, 13), (1, 17, 1, 17), (1, 18, 2, 0), (2, 5, 2, 10), (2, 11, 3, 0)]),
+        (NonZeroU32::new(8), [(1, 9, 1, 9), (1, 12, 1, 12), (1, 17, 1, 17), (1, 25, 1, 25), (1, 26, 2, 0), (2, 9, 2, 18), (2, 19, 3, 0)]),
+    ] {
+        let (tokens, metadata) = tok_ok_with_options(str, Options {
+            tab_width,
+            ..Options::default()
+        });
+        assert_eq!(
+            vec![
+                Ident("a".into()),
+                Ident("b".into()),
+                Integer(1),
+                Ident("c".into()),
+                Newline,
+                Text("d\te".into()),
+                Newline
+            ],
+            tokens
+        );
+        let offsets = [(1, 2), (4, 5), (6, 7), (8, 9), (9, 10), (13, 18), (18, 19)];
+        let expect = expect.iter().zip(offsets).map(|(&(start_line, start_column, end_line, end_column), (start_offset, end_offset))| {
+            Metadata::bounds(start_line, start_column, start_offset, end_line, end_column, end_offset)
+        }).collect::<Vec<_>>();
+        assert_eq!(expect, metadata, "for {tab_width:?}");
+    }
+}
+
+#[test]
+fn tab_stops_after_error() {
+    let fragments = tok_recover("1x\t2 \t\"a\n", Options {
+        tab_width: NonZeroU32::new(4),
+        ..Options::default()
+    });
+    assert_eq!(
+        vec![
+            Err("unparsable integer 1x (invalid digit found in string) at line 1, column 4".into()),
+            Ok((Integer(2), Metadata::bounds(1, 5, 3, 1, 5, 4))),
+            Err("unterminated literal at line 1, column 11".into()),
+            Ok((Newline, Metadata::bounds(1, 11, 8, 2, 0, 9))),
+        ],
+        fragments
+    );
+}
+
+#[test]
+fn line_terminators() {
+    let str = "a\r\nb\rc\u{85}d\u{2028}e\u{2029}f\ng";
+    let (tokens, metadata) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Ident("a".into()),
+            Newline,
+            Ident("b".into()),
+            Newline,
+            Ident("c".into()),
+            Newline,
+            Ident("d".into()),
+            Newline,
+            Ident("e".into()),
+            Newline,
+            Ident("f".into()),
+            Newline,
+            Ident("g".into()),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 2, 0, 3),
+            Metadata::bounds(2, 1, 3, 2, 1, 4),
+            Metadata::bounds(2, 2, 4, 3, 0, 5),
+            Metadata::bounds(3, 1, 5, 3, 1, 6),
+            Metadata::bounds(3, 2, 6, 4, 0, 8),
+            Metadata::bounds(4, 1, 8, 4, 1, 9),
+            Metadata::bounds(4, 2, 9, 5, 0, 12),
+            Metadata::bounds(5, 1, 12, 5, 1, 13),
+            Metadata::bounds(5, 2, 13, 6, 0, 16),
+            Metadata::bounds(6, 1, 16, 6, 1, 17),
+            Metadata::bounds(6, 2, 17, 7, 0, 18),
+            Metadata::bounds(7, 1, 18, 7, 1, 19),
+            Metadata::bounds(7, 2, 19, 8, 0, 19),
         ],
         metadata
     );
 }
 
+#[test]
+fn line_ending_recorded() {
+    for (str, expect) in [
+        ("a b", None),
+        ("a\n", Some(LineEnding::Lf)),
+        ("a\r\nb\nc\n", Some(LineEnding::CrLf)),
+        ("a\rb\r\n", Some(LineEnding::Cr)),
+        ("/* a\u{85}b */ c\n", Some(LineEnding::Nel)),
+        ("a\u{2028}", Some(LineEnding::LineSeparator)),
+        ("\u{2029}", Some(LineEnding::ParagraphSeparator)),
+    ] {
+        let mut tokeniser = Tokeniser::new(str, SymbolTable::default());
+        assert!(tokeniser.by_ref().all(|fragment| fragment.is_ok()));
+        assert_eq!(expect, tokeniser.line_ending(), "for {str:?}");
+    }
+}
+
+#[test]
+fn line_terminators_in_literals() {
+    let options = Options {
+        comments: Comments {
+            emit: true,
+            ..Comments::c_style()
+        },
+        ..Options::default()
+    };
+    let (tokens, _) = tok_ok_with_options("// a\r\n\"b \\\r\n  c\" \"\"\"\r\n  d\r\n    e\u{2028}  \"\"\"\r", options);
+    assert_eq!(
+        vec![
+            Comment("// a".into()),
+            Newline,
+            Text("b c".into()),
+            Text("d\n  e".into()),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(vec![Borrowed, NA, Owned, Owned, NA], is_owned(tokens));
+}
+
+#[test]
+fn line_terminators_end_literals_err() {
+    for (str, expect) in [
+        ("\"a\rb\"", "unterminated literal at line 1, column 3"),
+        ("\"a\u{2028}b\"", "unterminated literal at line 1, column 3"),
+        ("\"a\u{2029}b\"", "unterminated literal at line 1, column 3"),
+        ("\"a\u{85}b\"", "unterminated literal at line 1, column 3"),
+        ("\"a\r\nb\"", "unterminated literal at line 1, column 3"),
+        ("'\r'", "unterminated literal at line 1, column 2"),
+        ("b\"a\rb\"", "unterminated literal at line 1, column 4"),
+        ("b'\u{2028}'", "unterminated literal at line 1, column 3"),
+    ] {
+        let err = tok_err(str);
+        assert_eq!(expect, err.to_string(), "for {str:?}");
+    }
+}
+
+#[test]
+fn line_terminators_end_raw_literals_err() {
+    let err = tok_err("r#\"a\u{2028}b\"#");
+    assert_eq!("unterminated raw literal at line 1, column 1 (expected \"#)", err.to_string());
+}
+
+#[test]
+fn line_terminators_end_interpolated_text_err() {
+    let options = Options {
+        interpolation: true,
+        ..Options::default()
+    };
+    let err = tok_err_with_options("\"${a}\u{2028}\"", options);
+    assert_eq!("unterminated literal at line 1, column 6", err.to_string());
+}
+
 #[test]
 fn ident_colon_terminated() {
     let str = r#"first:second"#;
@@ -1033,10 +2046,10 @@ fn ident_colon_terminated() {
     assert_eq!(vec![Borrowed, NA, Borrowed, NA], is_owned(tokens));
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 5),
-            Metadata::bounds(1, 6, 1, 6),
-            Metadata::bounds(1, 7, 1, 12),
-            Metadata::bounds(1, 13, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 1, 6, 6),
+            Metadata::bounds(1, 7, 6, 1, 12, 12),
+            Metadata::bounds(1, 13, 12, 2, 0, 12),
         ],
         metadata
     );
@@ -1049,9 +2062,9 @@ fn boolean() {
     assert_eq!(vec![Boolean(true), Boolean(false), Newline], tokens);
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 4),
-            Metadata::bounds(1, 6, 1, 10),
-            Metadata::bounds(1, 11, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 4, 4),
+            Metadata::bounds(1, 6, 5, 1, 10, 10),
+            Metadata::bounds(1, 11, 10, 2, 0, 10),
         ],
         metadata
     );
@@ -1067,10 +2080,10 @@ fn boolean_comma_terminated() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 4),
-            Metadata::bounds(1, 6, 1, 10),
-            Metadata::bounds(1, 11, 1, 11),
-            Metadata::bounds(1, 12, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 4, 4),
+            Metadata::bounds(1, 6, 5, 1, 10, 10),
+            Metadata::bounds(1, 11, 10, 1, 11, 11),
+            Metadata::bounds(1, 12, 11, 2, 0, 11),
         ],
         metadata
     );
@@ -1093,11 +2106,11 @@ fn mixed_flat_sequence_of_tokens() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 5),
-            Metadata::bounds(1, 7, 1, 13),
-            Metadata::bounds(1, 14, 2, 0),
-            Metadata::bounds(2, 1, 2, 2),
-            Metadata::bounds(2, 3, 3, 0),
+            Metadata::bounds(1, 1, 0, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 2, 0, 14),
+            Metadata::bounds(2, 1, 14, 2, 2, 16),
+            Metadata::bounds(2, 3, 16, 3, 0, 16),
         ],
         metadata
     );
@@ -1121,13 +2134,13 @@ fn mixed_list_around_list() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 3, 1, 3),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 1, 5),
-            Metadata::bounds(1, 6, 1, 6),
-            Metadata::bounds(1, 7, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 3, 2, 1, 3, 3),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 1, 6, 6),
+            Metadata::bounds(1, 7, 6, 2, 0, 6),
         ],
         metadata
     );
@@ -1153,14 +2166,14 @@ fn mixed_list_nested() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 6),
-            Metadata::bounds(1, 8, 1, 8),
-            Metadata::bounds(1, 9, 1, 15),
-            Metadata::bounds(1, 16, 2, 0),
-            Metadata::bounds(2, 1, 2, 1),
-            Metadata::bounds(2, 2, 2, 2),
-            Metadata::bounds(2, 3, 3, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 6, 6),
+            Metadata::bounds(1, 8, 7, 1, 8, 8),
+            Metadata::bounds(1, 9, 8, 1, 15, 15),
+            Metadata::bounds(1, 16, 15, 2, 0, 16),
+            Metadata::bounds(2, 1, 16, 2, 1, 17),
+            Metadata::bounds(2, 2, 17, 2, 2, 18),
+            Metadata::bounds(2, 3, 18, 3, 0, 18),
         ],
         metadata
     );
@@ -1182,11 +2195,11 @@ fn mixed_list_with_one_item_trailing_comma() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 3, 1, 3),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 3, 2, 1, 3, 3),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 2, 0, 4),
         ],
         metadata
     );
@@ -1210,13 +2223,13 @@ fn mixed_list_with_many_items() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 5, 1, 5),
-            Metadata::bounds(1, 7, 1, 7),
-            Metadata::bounds(1, 8, 1, 8),
-            Metadata::bounds(1, 9, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 7, 6, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 1, 8, 8),
+            Metadata::bounds(1, 9, 8, 2, 0, 8),
         ],
         metadata
     );
@@ -1238,11 +2251,11 @@ fn mixed_relation_single_long_tail() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 3, 1, 3),
-            Metadata::bounds(1, 5, 1, 5),
-            Metadata::bounds(1, 6, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 3, 2, 1, 3, 3),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 2, 0, 5),
         ],
         metadata
     );
@@ -1266,13 +2279,13 @@ fn mixed_relation_multiple() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 3, 1, 3),
-            Metadata::bounds(1, 5, 1, 5),
-            Metadata::bounds(1, 6, 1, 6),
-            Metadata::bounds(1, 7, 1, 7),
-            Metadata::bounds(1, 8, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 3, 2, 1, 3, 3),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 1, 6, 6),
+            Metadata::bounds(1, 7, 6, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 2, 0, 7),
         ],
         metadata
     );
@@ -1298,16 +2311,594 @@ fn mixed_relation_inside_list() {
     );
     assert_eq!(
         vec![
-            Metadata::bounds(1, 1, 1, 1),
-            Metadata::bounds(1, 2, 1, 2),
-            Metadata::bounds(1, 3, 1, 3),
-            Metadata::bounds(1, 4, 1, 4),
-            Metadata::bounds(1, 6, 1, 6),
-            Metadata::bounds(1, 7, 1, 7),
-            Metadata::bounds(1, 8, 1, 8),
-            Metadata::bounds(1, 9, 1, 9),
-            Metadata::bounds(1, 10, 2, 0),
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 2, 2),
+            Metadata::bounds(1, 3, 2, 1, 3, 3),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 6, 5, 1, 6, 6),
+            Metadata::bounds(1, 7, 6, 1, 7, 7),
+            Metadata::bounds(1, 8, 7, 1, 8, 8),
+            Metadata::bounds(1, 9, 8, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 2, 0, 9),
+        ],
+        metadata
+    );
+}
+
+fn comments() -> Options {
+    Options {
+        comments: Comments::c_style(),
+        ..Options::default()
+    }
+}
+
+fn emit_comments() -> Options {
+    Options {
+        comments: Comments {
+            emit: true,
+            ..Comments::c_style()
+        },
+        ..Options::default()
+    }
+}
+
+#[test]
+fn line_comment_discarded() {
+    let str = "1 // one\n2";
+    let (tokens, metadata) = tok_ok_with_options(str, comments());
+    assert_eq!(vec![Integer(1), Newline, Integer(2), Newline], tokens);
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 9, 8, 2, 0, 9),
+            Metadata::bounds(2, 1, 9, 2, 1, 10),
+            Metadata::bounds(2, 2, 10, 3, 0, 10),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn line_comment_emitted() {
+    let str = "1 // one µ\n2//two";
+    let (tokens, metadata) = tok_ok_with_options(str, emit_comments());
+    assert_eq!(
+        vec![
+            Integer(1),
+            Comment("// one µ".into()),
+            Newline,
+            Integer(2),
+            Comment("//two".into()),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(vec![NA, Borrowed, NA, NA, Borrowed, NA], is_owned(tokens));
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 3, 2, 1, 10, 11),
+            Metadata::bounds(1, 11, 11, 2, 0, 12),
+            Metadata::bounds(2, 1, 12, 2, 1, 13),
+            Metadata::bounds(2, 2, 13, 2, 6, 18),
+            Metadata::bounds(2, 7, 18, 3, 0, 18),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn line_comment_after_symbol() {
+    let str = "a:// one";
+    let (tokens, _) = tok_ok_with_options(str, comments());
+    assert_eq!(vec![Ident("a".into()), Symbol(Ascii(b':')), Newline], tokens);
+}
+
+#[test]
+fn line_comment_inside_text() {
+    let str = r#""// not a comment""#;
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Text("// not a comment".into()), Newline], tokens);
+}
+
+#[test]
+fn block_comment_discarded() {
+    let str = "1 /* one\n two */ 2";
+    let (tokens, metadata) = tok_ok_with_options(str, comments());
+    assert_eq!(vec![Integer(1), Integer(2), Newline], tokens);
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(2, 9, 17, 2, 9, 18),
+            Metadata::bounds(2, 10, 18, 3, 0, 18),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn block_comment_emitted() {
+    let str = "1 /* one\n two */ 2";
+    let (tokens, metadata) = tok_ok_with_options(str, emit_comments());
+    assert_eq!(
+        vec![Integer(1), Comment("/* one\n two */".into()), Integer(2), Newline],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 3, 2, 2, 7, 16),
+            Metadata::bounds(2, 9, 17, 2, 9, 18),
+            Metadata::bounds(2, 10, 18, 3, 0, 18),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn block_comment_nested() {
+    let str = "/* a /* b */ c */x";
+    let (tokens, metadata) = tok_ok_with_options(str, emit_comments());
+    assert_eq!(
+        vec![Comment("/* a /* b */ c */".into()), Ident("x".into()), Newline],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 17, 17),
+            Metadata::bounds(1, 18, 17, 1, 18, 18),
+            Metadata::bounds(1, 19, 18, 2, 0, 18),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn block_comment_unterminated_err() {
+    let str = "1 /* a /* b */\n";
+    let err = tok_err_with_options(str, comments());
+    assert_eq!("unterminated comment at line 1, column 3", err.to_string());
+}
+
+#[test]
+fn comments_custom_syntax() {
+    let options = Options {
+        comments: Comments {
+            line: Some("#".into()),
+            block: Some(("#[".into(), "]#".into())),
+            emit: true,
+        },
+        ..Options::default()
+    };
+    let str = "a #[ b ]# c # d\n// e";
+    let (tokens, _) = tok_ok_with_options(str, options);
+    assert_eq!(
+        vec![
+            Ident("a".into()),
+            Comment("#[ b ]#".into()),
+            Ident("c".into()),
+            Comment("# d".into()),
+            Newline,
+            Symbol(Ascii(b'/')),
+            Symbol(Ascii(b'/')),
+            Ident("e".into()),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn comments_disabled() {
+    let options = Options {
+        comments: Comments::none(),
+        ..Options::default()
+    };
+    let str = "/* a */";
+    let (tokens, _) = tok_ok_with_options(str, options);
+    assert_eq!(
+        vec![
+            Symbol(Ascii(b'/')),
+            Symbol(Ascii(b'*')),
+            Ident("a".into()),
+            Symbol(Ascii(b'*')),
+            Symbol(Ascii(b'/')),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn comments_disabled_by_default() {
+    let str = "a // b";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Ident("a".into()),
+            Symbol(Ascii(b'/')),
+            Symbol(Ascii(b'/')),
+            Ident("b".into()),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn comment_delimiters_validated() {
+    for (comments, expect) in [
+        (Comments { line: Some("".into()), ..Comments::none() }, "invalid comment delimiter \"\" (empty)"),
+        (Comments { line: Some("#µ".into()), ..Comments::none() }, "invalid comment delimiter \"#µ\" (not ASCII)"),
+        (Comments { line: Some("rem".into()), ..Comments::none() }, "invalid comment delimiter \"rem\" (does not begin with a symbol character)"),
+        (Comments { block: Some(("/*".into(), "".into())), ..Comments::none() }, "invalid comment delimiter \"\" (empty)"),
+        (Comments { line: Some("--".into()), ..Comments::none() }, "invalid comment delimiter \"--\" (hides symbol --)"),
+        (Comments { block: Some(("+".into(), "+".into())), ..Comments::none() }, "invalid comment delimiter \"+\" (hides symbol ++)"),
+    ] {
+        let options = Options { comments, ..Options::default() };
+        let fragments: Vec<_> = Tokeniser::with_options("a", SymbolTable::default(), options).collect();
+        assert_eq!(1, fragments.len());
+        assert_eq!(expect, fragments[0].as_ref().unwrap_err().to_string());
+    }
+}
+
+#[test]
+fn comment_delimiters_beside_symbols() {
+    let options = Options {
+        comments: Comments { line: Some("#".into()), block: Some(("-(".into(), ")-".into())), emit: false },
+        ..Options::default()
+    };
+    let (tokens, _) = tok_ok_with_options("a -- b -( c )- d # e", options);
+    assert_eq!(
+        vec![
+            Ident("a".into()),
+            ExtendedSymbol(AsciiSlice(b"--".into())),
+            Ident("b".into()),
+            Ident("d".into()),
+            Newline
+        ],
+        tokens
+    );
+}
+
+fn angle_lists() -> Options {
+    Options {
+        angle_lists: true,
+        ..Options::default()
+    }
+}
+
+#[test]
+fn angle_list_attached_to_ident() {
+    let str = "Map<K, Vec<V>>";
+    let (tokens, metadata) = tok_ok_with_options(str, angle_lists());
+    assert_eq!(
+        vec![
+            Ident("Map".into()),
+            Left(Angle),
+            Ident("K".into()),
+            Symbol(Ascii(b',')),
+            Ident("Vec".into()),
+            Left(Angle),
+            Ident("V".into()),
+            Right(Angle),
+            Right(Angle),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 3, 3),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 5, 4, 1, 5, 5),
+            Metadata::bounds(1, 6, 5, 1, 6, 6),
+            Metadata::bounds(1, 8, 7, 1, 10, 10),
+            Metadata::bounds(1, 11, 10, 1, 11, 11),
+            Metadata::bounds(1, 12, 11, 1, 12, 12),
+            Metadata::bounds(1, 13, 12, 1, 13, 13),
+            Metadata::bounds(1, 14, 13, 1, 14, 14),
+            Metadata::bounds(1, 15, 14, 2, 0, 14),
+        ],
+        metadata
+    );
+}
+
+#[test]
+fn angle_list_comparison_operators() {
+    let str = "a < b > c";
+    let (tokens, _) = tok_ok_with_options(str, angle_lists());
+    assert_eq!(
+        vec![
+            Ident("a".into()),
+            Symbol(Ascii(b'<')),
+            Ident("b".into()),
+            Symbol(Ascii(b'>')),
+            Ident("c".into()),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn angle_list_with_nested_comparison() {
+    let str = "Array<(n > 1)>";
+    let (tokens, _) = tok_ok_with_options(str, angle_lists());
+    assert_eq!(
+        vec![
+            Ident("Array".into()),
+            Left(Angle),
+            Left(Paren),
+            Ident("n".into()),
+            Symbol(Ascii(b'>')),
+            Integer(1),
+            Right(Paren),
+            Right(Angle),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn angle_list_closed_after_symbol() {
+    let str = "Fn<a->";
+    let (tokens, _) = tok_ok_with_options(str, angle_lists());
+    assert_eq!(
+        vec![
+            Ident("Fn".into()),
+            Left(Angle),
+            Ident("a".into()),
+            Symbol(Ascii(b'-')),
+            Right(Angle),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn angle_list_not_opened_by_operators() {
+    for (str, operator) in [
+        ("x<5", vec![Symbol(Ascii(b'<'))]),
+        ("x<=5", vec![Symbol(Ascii(b'<')), Symbol(Ascii(b'='))]),
+        ("x<<5", vec![Symbol(Ascii(b'<')), Symbol(Ascii(b'<'))]),
+        ("x< 5", vec![Symbol(Ascii(b'<'))]),
+    ] {
+        let (tokens, _) = tok_ok_with_options(str, angle_lists());
+        let expected = [vec![Ident("x".into())], operator, vec![Integer(5), Newline]].concat();
+        assert_eq!(expected, tokens, "for input {str:?}");
+    }
+}
+
+#[test]
+fn angle_list_not_opened_without_closer() {
+    for (str, expected) in [
+        ("a<b", vec![Ident("a".into()), Symbol(Ascii(b'<')), Ident("b".into())]),
+        ("a<b\nc>", vec![Ident("a".into()), Symbol(Ascii(b'<')), Ident("b".into()), Newline, Ident("c".into()), Symbol(Ascii(b'>'))]),
+        ("(a<b, c) > d", vec![
+            Left(Paren),
+            Ident("a".into()),
+            Symbol(Ascii(b'<')),
+            Ident("b".into()),
+            Symbol(Ascii(b',')),
+            Ident("c".into()),
+            Right(Paren),
+            Symbol(Ascii(b'>')),
+            Ident("d".into()),
+        ]),
+        ("a<\"b>\"", vec![Ident("a".into()), Symbol(Ascii(b'<')), Text("b>".into())]),
+    ] {
+        let (tokens, _) = tok_ok_with_options(str, angle_lists());
+        assert_eq!([expected, vec![Newline]].concat(), tokens, "for input {str:?}");
+    }
+}
+
+#[test]
+fn angle_list_opened_by_unspaced_comparison() {
+    let str = "x<y>z";
+    let (tokens, _) = tok_ok_with_options(str, angle_lists());
+    assert_eq!(
+        vec![
+            Ident("x".into()),
+            Left(Angle),
+            Ident("y".into()),
+            Right(Angle),
+            Ident("z".into()),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn angle_list_not_closed_within_symbol() {
+    let mut symbol_table = SymbolTable::default();
+    symbol_table.add(SymbolString::try_from("->").unwrap()).unwrap();
+    let str = "Fn<a->b>";
+    let tokens = Tokeniser::with_options(str, symbol_table, angle_lists())
+        .map(|fragment| fragment.unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            Ident("Fn".into()),
+            Left(Angle),
+            Ident("a".into()),
+            ExtendedSymbol(AsciiSlice(Cow::Borrowed(b"->"))),
+            Ident("b".into()),
+            Right(Angle),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn angle_list_disabled() {
+    let str = "Vec<T>";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(
+        vec![
+            Ident("Vec".into()),
+            Symbol(Ascii(b'<')),
+            Ident("T".into()),
+            Symbol(Ascii(b'>')),
+            Newline
+        ],
+        tokens
+    );
+}
+
+fn signed_numbers() -> Options {
+    Options {
+        signed_numbers: true,
+        ..Options::default()
+    }
+}
+
+#[test]
+fn signed_numbers_folded() {
+    let str = "[-1, +2.5, -.5, -0xff, - 3]";
+    let (tokens, metadata) = tok_ok_with_options(str, signed_numbers());
+    assert_eq!(
+        vec![
+            Left(Bracket),
+            Signed(Minus, Box::new(Integer(1))),
+            Symbol(Ascii(b',')),
+            Signed(Plus, Box::new(Decimal(token::Decimal(2, 5, 1)))),
+            Symbol(Ascii(b',')),
+            Signed(Minus, Box::new(Decimal(token::Decimal(0, 5, 1)))),
+            Symbol(Ascii(b',')),
+            Signed(Minus, Box::new(RadixInteger(0xff, Hexadecimal))),
+            Symbol(Ascii(b',')),
+            Symbol(Ascii(b'-')),
+            Integer(3),
+            Right(Bracket),
+            Newline
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![
+            Metadata::bounds(1, 1, 0, 1, 1, 1),
+            Metadata::bounds(1, 2, 1, 1, 3, 3),
+            Metadata::bounds(1, 4, 3, 1, 4, 4),
+            Metadata::bounds(1, 6, 5, 1, 9, 9),
+            Metadata::bounds(1, 10, 9, 1, 10, 10),
+            Metadata::bounds(1, 12, 11, 1, 14, 14),
+            Metadata::bounds(1, 15, 14, 1, 15, 15),
+            Metadata::bounds(1, 17, 16, 1, 21, 21),
+            Metadata::bounds(1, 22, 21, 1, 22, 22),
+            Metadata::bounds(1, 24, 23, 1, 24, 24),
+            Metadata::bounds(1, 26, 25, 1, 26, 26),
+            Metadata::bounds(1, 27, 26, 1, 27, 27),
+            Metadata::bounds(1, 28, 27, 2, 0, 27),
         ],
         metadata
     );
 }
+
+#[test]
+fn signed_numbers_after_operand() {
+    let str = "1 -1 x-1 (-1) -1 /* c */ -1 \n-1";
+    let options = Options {
+        comments: Comments::c_style(),
+        ..signed_numbers()
+    };
+    let (tokens, _) = tok_ok_with_options(str, options);
+    assert_eq!(
+        vec![
+            Integer(1),
+            Symbol(Ascii(b'-')),
+            Integer(1),
+            Ident("x".into()),
+            Symbol(Ascii(b'-')),
+            Integer(1),
+            Left(Paren),
+            Signed(Minus, Box::new(Integer(1))),
+            Right(Paren),
+            Symbol(Ascii(b'-')),
+            Integer(1),
+            Symbol(Ascii(b'-')),
+            Integer(1),
+            Newline,
+            Signed(Minus, Box::new(Integer(1))),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn signed_numbers_after_symbol() {
+    let str = "a: -1e3 * +2";
+    let (tokens, _) = tok_ok_with_options(str, signed_numbers());
+    assert_eq!(
+        vec![
+            Ident("a".into()),
+            Symbol(Ascii(b':')),
+            Signed(Minus, Box::new(Scientific(token::Scientific(token::Decimal(1, 0, 0), 3)))),
+            Symbol(Ascii(b'*')),
+            Signed(Plus, Box::new(Integer(2))),
+            Newline
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn signed_numbers_disabled() {
+    let str = "-1";
+    let (tokens, _) = tok_ok(str);
+    assert_eq!(vec![Symbol(Ascii(b'-')), Integer(1), Newline], tokens);
+}
+
+#[test]
+fn recover_reports_every_error() {
+    let str = "a \"b\\q c\" d 'xy' e\n1.2.3 0x1g2 f \\ g\n\"h\n/* i";
+    assert_eq!(
+        vec![
+            Ok((Ident("a".into()), Metadata::bounds(1, 1, 0, 1, 1, 1))),
+            Err("unknown escape sequence \"q\" at line 1, column 6".into()),
+            Ok((Ident("d".into()), Metadata::bounds(1, 11, 10, 1, 11, 11))),
+            Err("unexpected character 'y' at line 1, column 15".into()),
+            Ok((Ident("e".into()), Metadata::bounds(1, 18, 17, 1, 18, 18))),
+            Ok((Newline, Metadata::bounds(1, 19, 18, 2, 0, 19))),
+            Ok((Decimal(token::Decimal(1, 2, 1)), Metadata::bounds(2, 1, 19, 2, 3, 22))),
+            Ok((Decimal(token::Decimal(0, 3, 1)), Metadata::bounds(2, 4, 22, 2, 5, 24))),
+            Err("unparsable integer 0x1g2 (invalid digit found in string) at line 2, column 12".into()),
+            Ok((Ident("f".into()), Metadata::bounds(2, 13, 31, 2, 13, 32))),
+            Err("unexpected character '\\' at line 2, column 15".into()),
+            Ok((Ident("g".into()), Metadata::bounds(2, 17, 35, 2, 17, 36))),
+            Ok((Newline, Metadata::bounds(2, 18, 36, 3, 0, 37))),
+            Err("unterminated literal at line 3, column 3".into()),
+            Ok((Newline, Metadata::bounds(3, 3, 39, 4, 0, 40))),
+            Err("unterminated comment at line 4, column 1".into()),
+        ],
+        tok_recover(str, comments())
+    );
+}
+
+#[test]
+fn recover_skips_rest_of_token() {
+    let str = "1x.5+2 \"${ 1x } a\" b\nc";
+    let options = Options {
+        interpolation: true,
+        ..Options::default()
+    };
+    assert_eq!(
+        vec![
+            Err("unparsable integer 1x (invalid digit found in string) at line 1, column 3".into()),
+            Ok((Symbol(token::Ascii(b'+')), Metadata::bounds(1, 5, 4, 1, 5, 5))),
+            Ok((Integer(2), Metadata::bounds(1, 6, 5, 1, 6, 6))),
+            Ok((InterpolationBegin, Metadata::bounds(1, 8, 7, 1, 8, 8))),
+            Ok((EmbedBegin, Metadata::bounds(1, 9, 8, 1, 10, 10))),
+            Err("unparsable integer 1x (invalid digit found in string) at line 1, column 14".into()),
+            Ok((Newline, Metadata::bounds(1, 21, 20, 2, 0, 21))),
+            Ok((Ident("c".into()), Metadata::bounds(2, 1, 21, 2, 1, 22))),
+            Ok((Newline, Metadata::bounds(2, 2, 22, 3, 0, 22))),
+        ],
+        tok_recover(str, options)
+    );
+}
@@ -1,4 +1,5 @@
 mod char_buffer;
+pub mod diagnostics;
 pub mod graphemes;
 pub mod lexer;
 pub mod metadata;
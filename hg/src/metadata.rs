@@ -1,15 +1,75 @@
 use std::fmt::{Display, Formatter};
+use std::num::NonZeroU32;
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Location {
     pub line: u32,
     pub column: u32,
+
+    /// The byte offset into the source. Where the location starts a region, this is the offset
+    /// of the first byte of the character at the line and column; otherwise it is the offset just
+    /// past the character's last byte. Hence, a region's text is `&source[start.offset..end.offset]`.
+    pub offset: usize,
 }
 
 impl Location {
+    #[inline]
+    pub const fn new(line: u32, column: u32, offset: usize) -> Self {
+        Self { line, column, offset }
+    }
+
     #[inline]
     pub fn before_start() -> Self {
-        Self { line: 1, column: 0 }
+        Self { line: 1, column: 0, offset: 0 }
+    }
+
+    /// The location with its column recounted in another unit, given the text of its line (less
+    /// the line break) and the distance between tab stops that the column was counted with (see
+    /// [`Options::tab_width`](crate::lexer::Options::tab_width)). Where the character at the
+    /// location spans several units, the column of a location that starts a region refers to its
+    /// first unit, and that of a location that ends a region to its last; `bound` says which this
+    /// is. Columns past the end of the line (such as that of the line break) are taken to count one
+    /// unit per character in either unit.
+    pub fn convert_column(&self, line: &str, tab_width: Option<NonZeroU32>, from: ColumnUnit, to: ColumnUnit, bound: Bound) -> Location {
+        let column = self.column as usize;
+        let (mut from_before, mut to_before) = (0, 0);
+        let mut convert = |atom: &str| {
+            let (from_width, to_width) = match tab_width {
+                Some(tab_width) if atom == "\t" => {
+                    let tab_width = tab_width.get() as usize;
+                    let tab_stop = |before: usize| (before + 1).div_ceil(tab_width) * tab_width;
+                    (tab_stop(from_before) - from_before, tab_stop(to_before) - to_before)
+                }
+                _ => (from.len(atom), to.len(atom))
+            };
+            if column <= from_before + from_width {
+                return Some(match bound {
+                    Bound::Start => to_before + 1,
+                    Bound::End => to_before + to_width
+                })
+            }
+            from_before += from_width;
+            to_before += to_width;
+            None
+        };
+        let atoms: Box<dyn Iterator<Item=&str>> = Box::new(
+            line.char_indices().map(|(offset, char)| &line[offset..offset + char.len_utf8()])
+        );
+        #[cfg(feature = "graphemes")]
+        let atoms: Box<dyn Iterator<Item=&str>> = if matches!(from, ColumnUnit::Graphemes) || matches!(to, ColumnUnit::Graphemes) {
+            Box::new(line.graphemes(true))
+        } else {
+            atoms
+        };
+        let converted = if column == 0 {
+            Some(0)
+        } else {
+            atoms.filter_map(&mut convert).next()
+        };
+        let column = converted.unwrap_or_else(|| to_before + column - from_before);
+        Location { column: column as u32, ..self.clone() }
     }
 }
 
@@ -19,6 +79,45 @@ impl Display for Location {
     }
 }
 
+/// The unit in which columns are counted. Whatever the unit, the first column of a line is 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnUnit {
+    /// UTF-8 bytes.
+    Bytes,
+
+    /// Unicode scalar values.
+    #[default]
+    Chars,
+
+    /// UTF-16 code units, as in the Language Server Protocol.
+    Utf16,
+
+    /// Extended grapheme clusters, each perceived as a single character. Requires the
+    /// `graphemes` feature.
+    #[cfg(feature = "graphemes")]
+    Graphemes,
+}
+
+impl ColumnUnit {
+    /// The length of the given text in this unit.
+    pub fn len(self, str: &str) -> usize {
+        match self {
+            ColumnUnit::Bytes => str.len(),
+            ColumnUnit::Chars => str.chars().count(),
+            ColumnUnit::Utf16 => str.encode_utf16().count(),
+            #[cfg(feature = "graphemes")]
+            ColumnUnit::Graphemes => str.graphemes(true).count()
+        }
+    }
+}
+
+/// Which end of a region a [`Location`] bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    Start,
+    End,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Metadata {
     pub start: Option<Location>,
@@ -68,34 +167,103 @@ impl Metadata {
         }
     }
 
-    #[cfg(test)]
-    pub(crate) fn bounds(start_line: u32, start_column: u32, end_line: u32, end_column: u32) -> Self {
+    /// Metadata for a region bounded at both ends, each given by its line, column and byte
+    /// offset (see [`Location::offset`]).
+    pub fn bounds(start_line: u32, start_column: u32, start_offset: usize, end_line: u32, end_column: u32, end_offset: usize) -> Self {
         debug_assert!(start_line <= end_line);
-        debug_assert!(
-            start_line == end_line && start_column <= end_column || start_line + 1 == end_line
-        );
+        debug_assert!(start_line < end_line || start_column <= end_column);
+        debug_assert!(start_offset <= end_offset);
         Self {
-            start: Some(Location {
-                line: start_line,
-                column: start_column,
-            }),
-            end: Some(Location {
-                line: end_line,
-                column: end_column,
-            }),
+            start: Some(Location::new(start_line, start_column, start_offset)),
+            end: Some(Location::new(end_line, end_column, end_offset)),
+        }
+    }
+
+    /// The span of the region, if bounded at both ends.
+    #[inline]
+    pub fn span(&self) -> Option<Span> {
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) => Some(Span { start: start.clone(), end: end.clone() }),
+            _ => None
+        }
+    }
+
+    /// The metadata of the smallest region covering this and the other one. Where either is not
+    /// bounded at both ends, the region runs from this one's start to the other's end, falling
+    /// back on whichever bound is specified.
+    pub fn merge(&self, other: &Metadata) -> Metadata {
+        match (self.span(), other.span()) {
+            (Some(span), Some(other)) => span.merge(&other).into(),
+            _ => Metadata {
+                start: self.start.clone().or_else(|| other.start.clone()),
+                end: other.end.clone().or_else(|| self.end.clone()),
+            }
+        }
+    }
+}
+
+/// A region of the source bounded at both ends. Like [`Metadata`], its line and column bounds
+/// are inclusive, whereas its byte offsets form a half-open range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Span {
+    /// The smallest span covering both this one and the other.
+    pub fn merge(&self, other: &Span) -> Span {
+        let start = if other.start.offset < self.start.offset { &other.start } else { &self.start };
+        let end = if other.end.offset > self.end.offset { &other.end } else { &self.end };
+        Span { start: start.clone(), end: end.clone() }
+    }
+
+    /// Whether the character at the given location lies within the span.
+    pub fn contains(&self, location: &Location) -> bool {
+        let position = (location.line, location.column);
+        (self.start.line, self.start.column) <= position && position <= (self.end.line, self.end.column)
+    }
+
+    /// The length of the span in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.end.offset - self.start.offset
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The text of the span, given the source it was taken from. Since the tokeniser terminates
+    /// its input with a line break if it lacks one, a span may extend past the end of the source;
+    /// it is truncated accordingly.
+    pub fn source_slice<'s>(&self, source: &'s str) -> &'s str {
+        let end = self.end.offset.min(source.len());
+        &source[self.start.offset.min(end)..end]
+    }
+}
+
+impl From<Span> for Metadata {
+    #[inline]
+    fn from(span: Span) -> Self {
+        Self {
+            start: Some(span.start),
+            end: Some(span.end),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::metadata::{Location, Metadata};
+    use crate::metadata::{Bound, ColumnUnit, Location, Metadata, Span};
+    use std::num::NonZeroU32;
 
     #[test]
     fn location_display() {
         assert_eq!(
             "line 2, column 3",
-            Location { line: 2, column: 3 }.to_string()
+            Location::new(2, 3, 10).to_string()
         );
     }
 
@@ -105,7 +273,7 @@ mod tests {
             (Metadata::unspecified(), "unspecified location"),
             (
                 Metadata {
-                    start: Some(Location { line: 2, column: 3 }),
+                    start: Some(Location::new(2, 3, 10)),
                     end: None,
                 },
                 "region after line 2, column 3",
@@ -113,28 +281,28 @@ mod tests {
             (
                 Metadata {
                     start: None,
-                    end: Some(Location { line: 2, column: 3 }),
+                    end: Some(Location::new(2, 3, 10)),
                 },
                 "region before line 2, column 3",
             ),
             (
                 Metadata {
-                    start: Some(Location { line: 2, column: 3 }),
-                    end: Some(Location { line: 2, column: 5 }),
+                    start: Some(Location::new(2, 3, 10)),
+                    end: Some(Location::new(2, 5, 12)),
                 },
                 "line 2, columns 3 to 5",
             ),
             (
                 Metadata {
-                    start: Some(Location { line: 2, column: 5 }),
-                    end: Some(Location { line: 2, column: 5 }),
+                    start: Some(Location::new(2, 5, 12)),
+                    end: Some(Location::new(2, 5, 12)),
                 },
                 "line 2, column 5",
             ),
             (
                 Metadata {
-                    start: Some(Location { line: 2, column: 3 }),
-                    end: Some(Location { line: 3, column: 1 }),
+                    start: Some(Location::new(2, 3, 10)),
+                    end: Some(Location::new(3, 1, 15)),
                 },
                 "line 2, column 3 to line 3, column 1",
             ),
@@ -142,4 +310,135 @@ mod tests {
             assert_eq!(expect, input.to_string(), "for input {input:?}")
         }
     }
+
+    #[test]
+    fn span_from_metadata() {
+        assert_eq!(None, Metadata::unspecified().span());
+        assert_eq!(None, Metadata { start: Some(Location::new(1, 1, 0)), end: None }.span());
+        let span = Metadata::bounds(1, 2, 1, 1, 4, 4).span().unwrap();
+        assert_eq!(Span { start: Location::new(1, 2, 1), end: Location::new(1, 4, 4) }, span);
+        assert_eq!(Metadata::bounds(1, 2, 1, 1, 4, 4), Metadata::from(span));
+    }
+
+    #[test]
+    fn span_merge() {
+        let a = Metadata::bounds(1, 2, 1, 1, 4, 4).span().unwrap();
+        let b = Metadata::bounds(2, 1, 6, 2, 3, 9).span().unwrap();
+        let expect = Metadata::bounds(1, 2, 1, 2, 3, 9).span().unwrap();
+        assert_eq!(expect, a.merge(&b));
+        assert_eq!(expect, b.merge(&a));
+        assert_eq!(a, a.merge(&a));
+    }
+
+    #[test]
+    fn metadata_merge() {
+        let a = Metadata::bounds(1, 2, 1, 1, 4, 4);
+        let b = Metadata::bounds(2, 1, 6, 2, 3, 9);
+        assert_eq!(Metadata::bounds(1, 2, 1, 2, 3, 9), b.merge(&a));
+        let open = Metadata { start: None, end: Some(Location::new(2, 3, 9)) };
+        assert_eq!(Metadata { start: a.start.clone(), end: open.end.clone() }, a.merge(&open));
+        assert_eq!(Metadata::unspecified(), Metadata::unspecified().merge(&Metadata::unspecified()));
+    }
+
+    #[test]
+    fn span_contains() {
+        let span = Metadata::bounds(1, 3, 2, 2, 2, 9).span().unwrap();
+        assert!(span.contains(&Location::new(1, 3, 2)));
+        assert!(span.contains(&Location::new(1, 7, 6)));
+        assert!(span.contains(&Location::new(2, 2, 8)));
+        assert!(!span.contains(&Location::new(1, 2, 1)));
+        assert!(!span.contains(&Location::new(2, 3, 9)));
+    }
+
+    #[test]
+    fn span_len_and_source_slice() {
+        let source = "ab\ncµd";
+        let span = Metadata::bounds(2, 1, 3, 2, 2, 6).span().unwrap();
+        assert_eq!(3, span.len());
+        assert!(!span.is_empty());
+        assert_eq!("cµ", span.source_slice(source));
+        let past_end = Metadata::bounds(2, 3, 6, 3, 0, 8).span().unwrap();
+        assert_eq!("d", past_end.source_slice(source));
+        let empty = Metadata::bounds(1, 1, 0, 1, 1, 0).span().unwrap();
+        assert!(empty.is_empty());
+        assert_eq!("", empty.source_slice(source));
+    }
+
+    #[test]
+    fn column_unit_len() {
+        let str = "aµ💣e\u{301}b";
+        assert_eq!(11, ColumnUnit::Bytes.len(str));
+        assert_eq!(6, ColumnUnit::Chars.len(str));
+        assert_eq!(7, ColumnUnit::Utf16.len(str));
+        #[cfg(feature = "graphemes")]
+        assert_eq!(5, ColumnUnit::Graphemes.len(str));
+    }
+
+    #[test]
+    fn convert_column() {
+        use ColumnUnit::{Bytes, Chars, Utf16};
+        let line = "aµ💣e\u{301}b";
+        for (column, from, to, bound, expect) in [
+            (0, Chars, Bytes, Bound::Start, 0),
+            (1, Chars, Bytes, Bound::Start, 1),
+            (3, Chars, Bytes, Bound::Start, 4),
+            (3, Chars, Bytes, Bound::End, 7),
+            (3, Chars, Utf16, Bound::Start, 3),
+            (3, Chars, Utf16, Bound::End, 4),
+            (7, Bytes, Chars, Bound::End, 3),
+            (5, Bytes, Chars, Bound::Start, 3),
+            (6, Utf16, Bytes, Bound::End, 10),
+            (7, Chars, Bytes, Bound::Start, 12),
+        ] {
+            let location = Location::new(3, column, 20);
+            let converted = location.convert_column(line, None, from, to, bound);
+            assert_eq!(Location::new(3, expect, 20), converted, "for column {column} from {from:?} to {to:?} at {bound:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "graphemes")]
+    fn convert_column_graphemes() {
+        use ColumnUnit::{Bytes, Chars, Graphemes};
+        let line = "aµ💣e\u{301}b";
+        for (column, from, to, bound, expect) in [
+            (5, Chars, Graphemes, Bound::Start, 4),
+            (5, Chars, Graphemes, Bound::End, 4),
+            (4, Graphemes, Chars, Bound::Start, 4),
+            (4, Graphemes, Chars, Bound::End, 5),
+            (4, Graphemes, Bytes, Bound::End, 10),
+            (7, Chars, Graphemes, Bound::Start, 6),
+            (12, Bytes, Graphemes, Bound::Start, 6),
+        ] {
+            let location = Location::new(3, column, 20);
+            let converted = location.convert_column(line, None, from, to, bound);
+            assert_eq!(Location::new(3, expect, 20), converted, "for column {column} from {from:?} to {to:?} at {bound:?}");
+        }
+    }
+
+    #[test]
+    fn convert_column_tab_stops() {
+        use ColumnUnit::{Bytes, Chars};
+        // with tab stops every 4 columns, the two-byte µ is at column 9 in chars but spans
+        // columns 9 and 10 in bytes, the tab after it taking up the difference; b is at column 13
+        let line = "\ta\tµ\tb";
+        let tab_width = NonZeroU32::new(4);
+        for (column, from, to, bound, expect) in [
+            (4, Chars, Bytes, Bound::End, 4),
+            (5, Chars, Bytes, Bound::Start, 5),
+            (6, Chars, Bytes, Bound::Start, 6),
+            (9, Chars, Bytes, Bound::Start, 9),
+            (9, Chars, Bytes, Bound::End, 10),
+            (10, Chars, Bytes, Bound::Start, 11),
+            (12, Chars, Bytes, Bound::End, 12),
+            (13, Chars, Bytes, Bound::Start, 13),
+            (10, Bytes, Chars, Bound::End, 9),
+            (11, Bytes, Chars, Bound::Start, 10),
+            (14, Chars, Bytes, Bound::Start, 14),
+        ] {
+            let location = Location::new(3, column, 20);
+            let converted = location.convert_column(line, tab_width, from, to, bound);
+            assert_eq!(Location::new(3, expect, 20), converted, "for column {column} from {from:?} to {to:?} at {bound:?}");
+        }
+    }
 }
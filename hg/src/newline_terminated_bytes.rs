@@ -1,18 +1,76 @@
 use std::str::Bytes;
+use crate::token::LineEnding;
 
+/// Yields the bytes of a string with their offsets, terminating the last line with a `\n` if it
+/// lacks a line terminator. Every line terminator (see [`LineEnding`]) is yielded as a single
+/// `\n` at the offset of its first byte.
 pub struct NewlineTerminatedBytes<'a> {
     bytes: Bytes<'a>,
     prev: Option<(usize, u8)>,
     offset: usize,
+    terminate: bool,
+    line_ending: Option<LineEnding>,
 }
 
 impl<'a> NewlineTerminatedBytes<'a> {
     #[inline(always)]
     pub fn new(bytes: Bytes<'a>) -> Self {
         Self {
-            bytes, prev: None, offset: 0,
+            bytes, prev: None, offset: 0, terminate: true, line_ending: None,
         }
     }
+
+    /// Yields the bytes as they are, without appending a newline.
+    #[inline(always)]
+    pub fn unterminated(bytes: Bytes<'a>) -> Self {
+        Self {
+            bytes, prev: None, offset: 0, terminate: false, line_ending: None,
+        }
+    }
+
+    /// The offset of the next byte to be read.
+    #[inline(always)]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The last byte yielded, with its offset.
+    #[inline(always)]
+    pub fn previous(&self) -> Option<(usize, u8)> {
+        self.prev
+    }
+
+    /// The first line terminator read, not counting an appended newline.
+    #[inline(always)]
+    pub fn line_ending(&self) -> Option<LineEnding> {
+        self.line_ending
+    }
+
+    /// Consumes the remainder of the line terminator starting with the given byte, if there is one.
+    #[inline(always)]
+    fn line_terminator(&mut self, byte: u8) -> Option<LineEnding> {
+        let mut ahead = self.bytes.clone();
+        let (line_ending, len) = line_terminator(&[byte, ahead.next().unwrap_or(0), ahead.next().unwrap_or(0)])?;
+        for _ in 1..len {
+            self.bytes.next();
+        }
+        self.offset += len - 1;
+        Some(line_ending)
+    }
+}
+
+/// The line terminator (see [`LineEnding`]) beginning the bytes, with its length in bytes.
+#[inline(always)]
+pub fn line_terminator(bytes: &[u8]) -> Option<(LineEnding, usize)> {
+    match bytes {
+        [b'\n', ..] => Some((LineEnding::Lf, 1)),
+        [b'\r', b'\n', ..] => Some((LineEnding::CrLf, 2)),
+        [b'\r', ..] => Some((LineEnding::Cr, 1)),
+        [0xC2, 0x85, ..] => Some((LineEnding::Nel, 2)),
+        [0xE2, 0x80, 0xA8, ..] => Some((LineEnding::LineSeparator, 3)),
+        [0xE2, 0x80, 0xA9, ..] => Some((LineEnding::ParagraphSeparator, 3)),
+        _ => None
+    }
 }
 
 impl Iterator for NewlineTerminatedBytes<'_> {
@@ -22,6 +80,9 @@ impl Iterator for NewlineTerminatedBytes<'_> {
     fn next(&mut self) -> Option<Self::Item> {
         let next = self.bytes.next();
         match next {
+            None if !self.terminate => {
+                self.prev = None
+            }
             None => {
                 match self.prev {
                     None => {
@@ -34,9 +95,23 @@ impl Iterator for NewlineTerminatedBytes<'_> {
                         self.prev = Some((offset + 1, b'\n'));
                     }
                 }
+                self.terminate = false; // yield nothing further
+            }
+            Some(byte @ (b'\n' | b'\r' | 0xC2 | 0xE2)) => {
+                let offset = self.offset;
+                match self.line_terminator(byte) {
+                    None => {
+                        self.prev = Some((offset, byte));
+                    }
+                    Some(line_ending) => {
+                        self.line_ending.get_or_insert(line_ending);
+                        self.prev = Some((offset, b'\n'));
+                    }
+                }
+                self.offset += 1;
             }
-            Some(grapheme) => {
-                self.prev = Some((self.offset, grapheme));
+            Some(byte) => {
+                self.prev = Some((self.offset, byte));
                 self.offset += 1;
             }
         }
@@ -47,6 +122,7 @@ impl Iterator for NewlineTerminatedBytes<'_> {
 #[cfg(test)]
 mod tests {
     use crate::newline_terminated_bytes::NewlineTerminatedBytes;
+    use crate::token::LineEnding;
 
     #[test]
     fn empty() {
@@ -54,6 +130,7 @@ mod tests {
         let mut nt = NewlineTerminatedBytes::new(str.bytes());
         assert_eq!(Some((0, b'\n')), nt.next());
         assert_eq!(None, nt.next());
+        assert_eq!(None, nt.next());
     }
 
     #[test]
@@ -79,6 +156,16 @@ mod tests {
         assert_eq!(None, nt.next());
     }
 
+    #[test]
+    fn unterminated() {
+        let str = "hi";
+        let mut nt = NewlineTerminatedBytes::unterminated(str.bytes());
+        assert_eq!(Some((0,  b'h')), nt.next());
+        assert_eq!(Some((1,  b'i')), nt.next());
+        assert_eq!(None, nt.next());
+        assert_eq!(None, nt.next());
+    }
+
     #[test]
     fn ending_without_newline() {
         let str = "hit";
@@ -89,4 +176,42 @@ mod tests {
         assert_eq!(Some((3,  b'\n')), nt.next());
         assert_eq!(None, nt.next());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn line_terminators() {
+        let str = "a\r\nb\rc\u{85}d\u{2028}e\u{2029}f\ng";
+        let mut nt = NewlineTerminatedBytes::new(str.bytes());
+        assert_eq!(None, nt.line_ending());
+        assert_eq!(Some((0,  b'a')), nt.next());
+        assert_eq!(Some((1,  b'\n')), nt.next());
+        assert_eq!(3, nt.offset());
+        assert_eq!(Some(LineEnding::CrLf), nt.line_ending());
+        assert_eq!(Some((3,  b'b')), nt.next());
+        assert_eq!(Some((4,  b'\n')), nt.next());
+        assert_eq!(Some((5,  b'c')), nt.next());
+        assert_eq!(Some((6,  b'\n')), nt.next());
+        assert_eq!(Some((8,  b'd')), nt.next());
+        assert_eq!(Some((9,  b'\n')), nt.next());
+        assert_eq!(Some((12,  b'e')), nt.next());
+        assert_eq!(Some((13,  b'\n')), nt.next());
+        assert_eq!(Some((16,  b'f')), nt.next());
+        assert_eq!(Some((17,  b'\n')), nt.next());
+        assert_eq!(Some((18,  b'g')), nt.next());
+        assert_eq!(Some((19,  b'\n')), nt.next());
+        assert_eq!(None, nt.next());
+        assert_eq!(Some(LineEnding::CrLf), nt.line_ending());
+    }
+
+    #[test]
+    fn multibyte_characters_sharing_lead_bytes() {
+        let str = "\u{80}\u{2027}";
+        let mut nt = NewlineTerminatedBytes::unterminated(str.bytes());
+        assert_eq!(Some((0, 0xC2)), nt.next());
+        assert_eq!(Some((1, 0x80)), nt.next());
+        assert_eq!(Some((2, 0xE2)), nt.next());
+        assert_eq!(Some((3, 0x80)), nt.next());
+        assert_eq!(Some((4, 0xA7)), nt.next());
+        assert_eq!(None, nt.next());
+        assert_eq!(None, nt.line_ending());
+    }
+}
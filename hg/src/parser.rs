@@ -1,3 +1,4 @@
+use std::fmt::{Display, Formatter};
 use std::mem;
 use thiserror::Error;
 use crate::lexer;
@@ -5,7 +6,7 @@ use crate::lexer::Fragment;
 use crate::metadata::{Location, Metadata};
 use crate::parser::fragment_stream::{FragmentStream};
 use crate::token::{Ascii, ListDelimiter, Token};
-use crate::tree::{Node, Phrase, Verse};
+use crate::tree::{Node, Part, Phrase, Verse};
 
 mod fragment_stream;
 
@@ -17,192 +18,444 @@ pub enum Error<'a> {
     #[error("unterminated container")]
     UnterminatedContainer,
     
-    #[error("unterminated list")]
-    UnterminatedList,
+    /// Holds the metadata of the opening delimiter.
+    #[error("unterminated list at {0}")]
+    UnterminatedList(Metadata),
 
-    #[error("unterminated relation")]
-    UnterminatedRelation,
+    /// Holds the metadata of the separating colon.
+    #[error("unterminated relation at {0}")]
+    UnterminatedRelation(Metadata),
 
     #[error("unterminated prefix")]
     UnterminatedPrefix,
 
-    #[error("unterminated phrase")]
-    UnterminatedPhrase,
+    #[error("unterminated phrase at {0}")]
+    UnterminatedPhrase(Metadata),
 
-    #[error("unexpected token {0:?}")]
-    UnexpectedToken(Token<'a>),
+    /// Holds the metadata of the opening quote.
+    #[error("unterminated interpolation at {0}")]
+    UnterminatedInterpolation(Metadata),
 
-    #[error("empty verse")]
-    EmptyVerse,
+    /// The token is boxed, as are the metadata of a mismatched delimiter, to keep the error small.
+    #[error("unexpected token {0:?} at {1}")]
+    UnexpectedToken(Box<Token<'a>>, Metadata),
 
-    #[error("empty relation segment")]
-    EmptyRelationSegment,
+    /// Holds the opening delimiter and its metadata, followed by the closing delimiter that does
+    /// not match it and its metadata, and the likely fix.
+    #[error("mismatched closing delimiter '{close}' at {3} for '{open}' at {1}; {4}", open = .0.left(), close = .2.right())]
+    MismatchedDelimiter(ListDelimiter, Box<Metadata>, ListDelimiter, Box<Metadata>, Suggestion),
+
+    /// Holds the metadata of the comma that ends the empty verse.
+    #[error("empty verse at {0}")]
+    EmptyVerse(Metadata),
+
+    /// Holds the metadata of the separating colon.
+    #[error("empty relation segment at {0}")]
+    EmptyRelationSegment(Metadata),
+
+    /// Holds the metadata of the expression's delimiters.
+    #[error("empty embedded expression at {0}")]
+    EmptyEmbed(Metadata),
+}
+
+/// The likely fix for a [mismatched delimiter](Error::MismatchedDelimiter), inferred from the
+/// lists enclosing the mismatched one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suggestion {
+    /// The closer of the given delimiter is missing; the mismatched one belongs to an enclosing list.
+    Insert(ListDelimiter),
+    /// The mismatched closer should have been that of the given delimiter.
+    Replace(ListDelimiter),
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Suggestion::Insert(delimiter) => write!(f, "perhaps a '{}' is missing", delimiter.right()),
+            Suggestion::Replace(delimiter) => write!(f, "perhaps it should be '{}'", delimiter.right()),
+        }
+    }
+}
+
+/// The errors of a parse. A fail-fast parse stops at the first error, whereas a recovering parse
+/// records it and carries on.
+struct Diagnostics<'a> {
+    errors: Option<Vec<Error<'a>>>,
+
+    /// The delimiters of the lists enclosing the current token, innermost last.
+    lists: Vec<ListDelimiter>,
+
+    /// Whether a construct was cut short by the end of the input, which then accounts for the
+    /// missing end of the phrase containing it.
+    truncated: bool,
+}
+
+impl<'a> Diagnostics<'a> {
+    #[inline]
+    fn report(&mut self, err: Error<'a>) -> Result<(), Box<Error<'a>>> {
+        match &mut self.errors {
+            None => Err(err.into()),
+            Some(errors) => {
+                errors.push(err);
+                Ok(())
+            }
+        }
+    }
 }
 
 #[inline]
 pub fn parse<'a, I: IntoIterator<Item=Fragment<'a>>>(into_iter: I) -> Result<Option<Verse<'a>>, Error<'a>> {
     let mut fragments = FragmentStream::from(into_iter.into_iter());
+    parse_verse(&mut fragments, &mut Diagnostics { errors: None, lists: vec![], truncated: false }).map_err(|err| *err)
+}
+
+/// Parses as much as possible, returning a best-effort verse together with every error
+/// encountered. A missing part of a construct, such as an empty relation segment or the closer
+/// of an unterminated list, is stood in for by a [`Node::Error`] placeholder, keeping whatever
+/// was parsed around it. Stray closing delimiters, commas and interpolation tokens are skipped,
+/// whereas a mismatched closing delimiter ends the list it mismatches. To also collect every lexical
+/// error, use a tokeniser with [`recover`](crate::lexer::Options::recover) enabled.
+pub fn parse_recovering<'a, I: IntoIterator<Item=Fragment<'a>>>(into_iter: I) -> (Option<Verse<'a>>, Vec<Error<'a>>) {
+    let mut fragments = FragmentStream::from(into_iter.into_iter());
+    let mut diagnostics = Diagnostics { errors: Some(vec![]), lists: vec![], truncated: false };
+    let verse = parse_verse(&mut fragments, &mut diagnostics).unwrap_or_else(|_| unreachable!()); // errors are reported instead
+    (verse, diagnostics.errors.unwrap_or_default())
+}
+
+#[inline]
+fn parse_verse<'a, I: Iterator<Item=Fragment<'a>>>(fragments: &mut FragmentStream<'a, I>, diagnostics: &mut Diagnostics<'a>) -> Result<Option<Verse<'a>>, Box<Error<'a>>> {
     let mut verse = vec![];
     let mut phrase = vec![];
     while let Some(fragment) = fragments.next() {
-        let (token, metadata) = fragment?;
+        let (token, metadata) = match fragment {
+            Ok(fragment) => fragment,
+            Err(err) => {
+                diagnostics.report(err.into())?;
+                continue
+            }
+        };
         match token {
             Token::Newline => {
                 if !phrase.is_empty() {
                     let phrase: Vec<Node> = mem::take(&mut phrase);
-                    let start = phrase[0].metadata().start.clone();
-                    let end = phrase[phrase.len() - 1].metadata().end.clone();
-                    verse.push(Phrase::new(phrase, Metadata { start, end }));
+                    let metadata = span(&phrase);
+                    verse.push(Phrase::new(phrase, metadata));
                 }
             }
             Token::Left(delimiter) => {
-                let child = parse_list(metadata.start, delimiter, &mut fragments)?;
+                let child = parse_list(metadata, delimiter, fragments, diagnostics)?;
+                phrase.push(child);
+            }
+            Token::InterpolationBegin => {
+                let child = parse_interpolation(metadata, fragments, diagnostics)?;
                 phrase.push(child);
             }
             Token::Symbol(Ascii(b':')) => {
-                let head = relation_head(&mut phrase)?;
-                let child = parse_relation(head, &mut fragments)?;
+                let head = relation_head(&mut phrase, &metadata, diagnostics)?;
+                let child = parse_relation(head, metadata, fragments, diagnostics)?;
                 phrase.push(child);
             }
-            Token::Symbol(Ascii(b',')) | Token::Right(_) => {
-                return Err(Error::UnexpectedToken(token))
+            Token::Symbol(Ascii(b',')) | Token::Right(_) | Token::TextSegment(_) | Token::EmbedBegin | Token::EmbedEnd | Token::InterpolationEnd => {
+                diagnostics.report(Error::UnexpectedToken(token.into(), metadata))?;
             },
-            Token::Text(_) | Token::Character(_) | Token::Integer(_) | Token::Decimal(_) | Token::Boolean(_) | Token::Ident(_) | Token::Symbol(_) | Token::ExtendedSymbol(_) => {
+            Token::Comment(_) => {}
+            Token::Text(_) | Token::Character(_) | Token::Bytes(_) | Token::Byte(_) | Token::Integer(_) | Token::RadixInteger(_, _) | Token::BigInteger(_) | Token::Decimal(_) | Token::BigDecimal(_) | Token::Scientific(_) | Token::Signed(_, _) | Token::Boolean(_) | Token::Ident(_) | Token::Symbol(_) | Token::ExtendedSymbol(_) => {
                 phrase.push(Node::Raw(token, metadata));
             }
         }
     }
 
-    if phrase.is_empty() {
-        if verse.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(Verse::new(verse)))
+    if !phrase.is_empty() {
+        let metadata = span(&phrase);
+        if !diagnostics.truncated {
+            diagnostics.report(Error::UnterminatedPhrase(metadata.clone()))?;
         }
+        verse.push(Phrase::new(phrase, metadata));
+    }
+    if verse.is_empty() {
+        Ok(None)
     } else {
-        Err(Error::UnterminatedPhrase)
+        Ok(Some(Verse::new(verse)))
     }
 }
 
 #[inline]
-fn parse_list<'a, I: Iterator<Item=Fragment<'a>>>(start: Option<Location>, left_delimiter: ListDelimiter, fragments: &mut FragmentStream<'a, I>) -> Result<Node<'a>, Error<'a>> {
+fn parse_list<'a, I: Iterator<Item=Fragment<'a>>>(opener: Metadata, left_delimiter: ListDelimiter, fragments: &mut FragmentStream<'a, I>, diagnostics: &mut Diagnostics<'a>) -> Result<Node<'a>, Box<Error<'a>>> {
+    let start = opener.start.clone();
     let mut verses = vec![];
     let mut verse = vec![];
     let mut phrase = vec![];
+    diagnostics.lists.push(left_delimiter);
     loop {
         if let Some(fragment) = fragments.next() {
-            let (token, metadata) = fragment?;
+            let (token, metadata) = match fragment {
+                Ok(fragment) => fragment,
+                Err(err) => {
+                    diagnostics.report(err.into())?;
+                    continue
+                }
+            };
             match token {
                 Token::Newline => {
                     if !phrase.is_empty() {
                         let phrase: Vec<Node> = mem::take(&mut phrase);
-                        let start = phrase[0].metadata().start.clone();
-                        let end = phrase[phrase.len() - 1].metadata().end.clone();
-                        verse.push(Phrase::new(phrase, Metadata { start, end }));
+                        let metadata = span(&phrase);
+                        verse.push(Phrase::new(phrase, metadata));
                     }
                 }
                 Token::Left(delimiter) => {
-                    let child = parse_list(metadata.start, delimiter, fragments)?;
+                    let child = parse_list(metadata, delimiter, fragments, diagnostics)?;
+                    phrase.push(child);
+                }
+                Token::InterpolationBegin => {
+                    let child = parse_interpolation(metadata, fragments, diagnostics)?;
                     phrase.push(child);
                 }
                 Token::Symbol(Ascii(b',')) => {
                     if !phrase.is_empty() {
                         let phrase = mem::take(&mut phrase);
-                        let start = phrase[0].metadata().start.clone();
-                        let end = phrase[phrase.len() - 1].metadata().end.clone();
-                        verse.push(Phrase::new(phrase, Metadata { start, end }));
+                        let metadata = span(&phrase);
+                        verse.push(Phrase::new(phrase, metadata));
                     }
                     if verse.is_empty() {
-                        return Err(Error::EmptyVerse)
+                        diagnostics.report(Error::EmptyVerse(metadata))?;
+                    } else {
+                        let verse = mem::take(&mut verse);
+                        verses.push(Verse::new(verse));
                     }
-                    let verse = mem::take(&mut verse);
-                    verses.push(Verse::new(verse));
                 }
                 Token::Symbol(Ascii(b':')) => {
-                    let head = relation_head(&mut phrase)?;
-                    let child = parse_relation(head, fragments)?;
+                    let head = relation_head(&mut phrase, &metadata, diagnostics)?;
+                    let child = parse_relation(head, metadata, fragments, diagnostics)?;
                     phrase.push(child);
                 }
                 Token::Right(right_delimiter) => {
-                    return if left_delimiter == right_delimiter {
-                        let end = metadata.end;
-                        if !phrase.is_empty() {
-                            let start = phrase[0].metadata().start.clone();
-                            let end = phrase[phrase.len() - 1].metadata().end.clone();
-                            verse.push(Phrase::new(phrase, Metadata { start, end }));
-                        }
-                        if !verse.is_empty() {
-                            verses.push(Verse::new(verse));
-                        }
-                        Ok(Node::List(verses, Metadata { start, end }))
+                    diagnostics.lists.pop();
+                    let end = if left_delimiter == right_delimiter {
+                        metadata.end
+                    } else if diagnostics.lists.contains(&right_delimiter) {
+                        // likely closes an enclosing list, so leave it to that list and end this one
+                        // after its contents
+                        let end = phrase.last().map(|node| node.metadata().end.clone())
+                            .or_else(|| verse.last().map(|phrase| phrase.metadata().end.clone()))
+                            .or_else(|| verses.last().map(|verse| verse.metadata().end))
+                            .unwrap_or_else(|| opener.end.clone());
+                        diagnostics.report(Error::MismatchedDelimiter(left_delimiter, opener.into(), right_delimiter, metadata.clone().into(), Suggestion::Insert(left_delimiter)))?;
+                        fragments.stash(Ok((token, metadata)));
+                        end
                     } else {
-                        Err(Error::UnexpectedToken(Token::Right(right_delimiter)))
+                        diagnostics.report(Error::MismatchedDelimiter(left_delimiter, opener.into(), right_delimiter, metadata.clone().into(), Suggestion::Replace(left_delimiter)))?;
+                        metadata.end
+                    };
+                    if !phrase.is_empty() {
+                        let metadata = span(&phrase);
+                        verse.push(Phrase::new(phrase, metadata));
+                    }
+                    if !verse.is_empty() {
+                        verses.push(Verse::new(verse));
                     }
+                    return Ok(Node::List(left_delimiter, verses, Metadata { start, end }))
                 },
-                Token::Text(_) | Token::Character(_) | Token::Integer(_) | Token::Decimal(_) | Token::Boolean(_) | Token::Ident(_) | Token::Symbol(_) | Token::ExtendedSymbol(_)=> {
+                Token::TextSegment(_) | Token::EmbedBegin | Token::EmbedEnd | Token::InterpolationEnd => {
+                    diagnostics.report(Error::UnexpectedToken(token.into(), metadata))?;
+                }
+                Token::Comment(_) => {}
+                Token::Text(_) | Token::Character(_) | Token::Bytes(_) | Token::Byte(_) | Token::Integer(_) | Token::RadixInteger(_, _) | Token::BigInteger(_) | Token::Decimal(_) | Token::BigDecimal(_) | Token::Scientific(_) | Token::Signed(_, _) | Token::Boolean(_) | Token::Ident(_) | Token::Symbol(_) | Token::ExtendedSymbol(_)=> {
                     phrase.push(Node::Raw(token, metadata));
                 }
             }
         } else {
-            return Err(Error::UnterminatedList)
+            diagnostics.lists.pop();
+            diagnostics.report(Error::UnterminatedList(opener))?;
+            diagnostics.truncated = true;
+            let end = fragments.last_end();
+            phrase.push(Node::Error(Metadata { start: end.clone(), end: end.clone() })); // the missing closer
+            let metadata = span(&phrase);
+            verse.push(Phrase::new(phrase, metadata));
+            verses.push(Verse::new(verse));
+            return Ok(Node::List(left_delimiter, verses, Metadata { start, end }))
+        }
+    }
+}
+
+/// The metadata spanning the given nodes, of which there must be at least one.
+#[inline]
+fn span(nodes: &[Node]) -> Metadata {
+    nodes[0].metadata().merge(nodes[nodes.len() - 1].metadata())
+}
+
+/// Takes the last node of a phrase as the head of a relation, substituting a placeholder at the
+/// given colon if there is none.
+#[inline]
+fn relation_head<'a>(nodes: &mut Vec<Node<'a>>, colon: &Metadata, diagnostics: &mut Diagnostics<'a>) -> Result<Node<'a>, Box<Error<'a>>> {
+    match nodes.pop() {
+        Some(node) => Ok(node),
+        None => {
+            diagnostics.report(Error::EmptyRelationSegment(colon.clone()))?;
+            Ok(Node::Error(colon.clone()))
         }
     }
 }
 
+/// Makes the tail of a relation separated by the given colon, substituting a placeholder at the
+/// given location if it is empty.
 #[inline]
-fn relation_head<'a>(nodes: &mut Vec<Node<'a>>) -> Result<Node<'a>, Error<'a>> {
-    if !nodes.is_empty() {
-        Ok(nodes.remove(nodes.len() - 1))
+fn relation_tail<'a>(tail: Vec<Node<'a>>, colon: &Metadata, location: &Option<Location>, diagnostics: &mut Diagnostics<'a>) -> Result<Phrase<'a>, Box<Error<'a>>> {
+    if !tail.is_empty() {
+        let metadata = span(&tail);
+        Ok(Phrase::new(tail, metadata))
     } else {
-        Err(Error::EmptyRelationSegment)
+        diagnostics.report(Error::EmptyRelationSegment(colon.clone()))?;
+        let metadata = Metadata { start: location.clone(), end: location.clone() };
+        Ok(Phrase::new(vec![Node::Error(metadata.clone())], metadata))
     }
 }
 
 #[inline]
-fn parse_relation<'a, I: Iterator<Item=Fragment<'a>>>(head: Node<'a>, fragments: &mut FragmentStream<'a, I>) -> Result<Node<'a>, Error<'a>> {
+fn parse_relation<'a, I: Iterator<Item=Fragment<'a>>>(head: Node<'a>, colon: Metadata, fragments: &mut FragmentStream<'a, I>, diagnostics: &mut Diagnostics<'a>) -> Result<Node<'a>, Box<Error<'a>>> {
     let mut tail = vec![];
     loop {
         if let Some(fragment) = fragments.next() {
-            let (token, metadata) = fragment?;
+            let (token, metadata) = match fragment {
+                Ok(fragment) => fragment,
+                Err(err) => {
+                    diagnostics.report(err.into())?;
+                    continue
+                }
+            };
             match token {
                 Token::Left(delimiter) => {
-                    let child = parse_list(metadata.start, delimiter, fragments)?;
+                    let child = parse_list(metadata, delimiter, fragments, diagnostics)?;
+                    tail.push(child);
+                }
+                Token::InterpolationBegin => {
+                    let child = parse_interpolation(metadata, fragments, diagnostics)?;
                     tail.push(child);
                 }
-                Token::Right(_) | Token::Symbol(Ascii(b',')) | Token::Newline => {
+                Token::Right(_) | Token::Symbol(Ascii(b',')) | Token::Newline | Token::EmbedEnd => {
+                    let phrase = relation_tail(tail, &colon, &metadata.start, diagnostics)?;
                     fragments.stash(Ok((token, metadata))); // restore token for the parent parser
-                    return if !tail.is_empty() {
-                        let head_start = head.metadata().start.clone();
-                        let tail_start = tail[0].metadata().start.clone();
-                        let tail_end = tail[tail.len() - 1].metadata().end.clone();
-                        let phrase = Phrase::new(tail, Metadata { start: tail_start, end: tail_end.clone() });
-                        Ok(Node::Relation(Box::new(head), phrase, Metadata { start: head_start, end: tail_end }))
-                    } else {
-                        Err(Error::EmptyRelationSegment)
-                    }
+                    let span = head.metadata().merge(phrase.metadata());
+                    return Ok(Node::Relation(Box::new(head), phrase, span))
                 }
                 Token::Symbol(Ascii(b':')) => {
-                    return if !tail.is_empty() {
-                        let tail_start = tail[0].metadata().start.clone();
-                        let tail_end = tail[tail.len() - 1].metadata().end.clone();
-                        let head_start = head.metadata().start.clone();
-                        let phrase = Phrase::new(tail, Metadata { start: tail_start, end: tail_end.clone()});
-                        let wrapped = Node::Relation(Box::new(head), phrase, Metadata { start: head_start, end: tail_end });
-                        let wrapper = parse_relation(wrapped, fragments)?;
-                        Ok(wrapper)
-                    } else {
-                        Err(Error::EmptyRelationSegment)
-                    }
+                    let phrase = relation_tail(tail, &colon, &metadata.start, diagnostics)?;
+                    let span = head.metadata().merge(phrase.metadata());
+                    let wrapped = Node::Relation(Box::new(head), phrase, span);
+                    let wrapper = parse_relation(wrapped, metadata, fragments, diagnostics)?;
+                    return Ok(wrapper)
                 },
-                Token::Text(_) | Token::Character(_) | Token::Integer(_) | Token::Decimal(_) | Token::Boolean(_) | Token::Ident(_) | Token::Symbol(_) | Token::ExtendedSymbol(_) => {
+                Token::TextSegment(_) | Token::EmbedBegin | Token::InterpolationEnd => {
+                    diagnostics.report(Error::UnexpectedToken(token.into(), metadata))?;
+                }
+                Token::Comment(_) => {}
+                Token::Text(_) | Token::Character(_) | Token::Bytes(_) | Token::Byte(_) | Token::Integer(_) | Token::RadixInteger(_, _) | Token::BigInteger(_) | Token::Decimal(_) | Token::BigDecimal(_) | Token::Scientific(_) | Token::Signed(_, _) | Token::Boolean(_) | Token::Ident(_) | Token::Symbol(_) | Token::ExtendedSymbol(_) => {
                     tail.push(Node::Raw(token, metadata))
                 }
             }
         } else {
-            return Err(Error::UnterminatedRelation)
+            diagnostics.report(Error::UnterminatedRelation(colon))?;
+            diagnostics.truncated = true;
+            let phrase = if tail.is_empty() {
+                let end = fragments.last_end();
+                let metadata = Metadata { start: end.clone(), end };
+                Phrase::new(vec![Node::Error(metadata.clone())], metadata)
+            } else {
+                let metadata = span(&tail);
+                Phrase::new(tail, metadata)
+            };
+            let span = head.metadata().merge(phrase.metadata());
+            return Ok(Node::Relation(Box::new(head), phrase, span))
+        }
+    }
+}
+
+#[inline]
+fn parse_interpolation<'a, I: Iterator<Item=Fragment<'a>>>(opener: Metadata, fragments: &mut FragmentStream<'a, I>, diagnostics: &mut Diagnostics<'a>) -> Result<Node<'a>, Box<Error<'a>>> {
+    let start = opener.start.clone();
+    let mut parts = vec![];
+    while let Some(fragment) = fragments.next() {
+        let (token, metadata) = match fragment {
+            Ok(fragment) => fragment,
+            Err(err) => {
+                diagnostics.report(err.into())?;
+                continue
+            }
+        };
+        match token {
+            Token::TextSegment(str) => {
+                parts.push(Part::Text(str, metadata));
+            }
+            Token::EmbedBegin => {
+                // an embedded expression cannot close the lists around its interpolation
+                let lists = mem::take(&mut diagnostics.lists);
+                let phrase = parse_embed(metadata, fragments, diagnostics);
+                diagnostics.lists = lists;
+                match phrase? {
+                    Some(phrase) => parts.push(Part::Expression(phrase)),
+                    None => break
+                }
+            }
+            Token::InterpolationEnd => {
+                let end = metadata.end;
+                return Ok(Node::Interpolation(parts, Metadata { start, end }))
+            }
+            _ => {
+                diagnostics.report(Error::UnexpectedToken(token.into(), metadata))?;
+            }
+        }
+    }
+    diagnostics.report(Error::UnterminatedInterpolation(opener))?;
+    diagnostics.truncated = true;
+    Ok(Node::Error(Metadata { start, end: fragments.last_end() }))
+}
+
+/// Parses an embedded expression after its opener, returning `None` if the input ends first.
+#[inline]
+fn parse_embed<'a, I: Iterator<Item=Fragment<'a>>>(opener: Metadata, fragments: &mut FragmentStream<'a, I>, diagnostics: &mut Diagnostics<'a>) -> Result<Option<Phrase<'a>>, Box<Error<'a>>> {
+    let mut phrase = vec![];
+    while let Some(fragment) = fragments.next() {
+        let (token, metadata) = match fragment {
+            Ok(fragment) => fragment,
+            Err(err) => {
+                diagnostics.report(err.into())?;
+                continue
+            }
+        };
+        match token {
+            Token::Left(delimiter) => {
+                let child = parse_list(metadata, delimiter, fragments, diagnostics)?;
+                phrase.push(child);
+            }
+            Token::InterpolationBegin => {
+                let child = parse_interpolation(metadata, fragments, diagnostics)?;
+                phrase.push(child);
+            }
+            Token::Symbol(Ascii(b':')) => {
+                let head = relation_head(&mut phrase, &metadata, diagnostics)?;
+                let child = parse_relation(head, metadata, fragments, diagnostics)?;
+                phrase.push(child);
+            }
+            Token::EmbedEnd => {
+                return if !phrase.is_empty() {
+                    let metadata = span(&phrase);
+                    Ok(Some(Phrase::new(phrase, metadata)))
+                } else {
+                    diagnostics.report(Error::EmptyEmbed(opener.merge(&metadata)))?;
+                    Ok(Some(Phrase::new(vec![Node::Error(metadata.clone())], metadata)))
+                }
+            }
+            Token::Symbol(Ascii(b',')) | Token::Right(_) | Token::Newline | Token::TextSegment(_) | Token::EmbedBegin | Token::InterpolationEnd => {
+                diagnostics.report(Error::UnexpectedToken(token.into(), metadata))?;
+            }
+            Token::Comment(_) => {}
+            Token::Text(_) | Token::Character(_) | Token::Bytes(_) | Token::Byte(_) | Token::Integer(_) | Token::RadixInteger(_, _) | Token::BigInteger(_) | Token::Decimal(_) | Token::BigDecimal(_) | Token::Scientific(_) | Token::Signed(_, _) | Token::Boolean(_) | Token::Ident(_) | Token::Symbol(_) | Token::ExtendedSymbol(_) => {
+                phrase.push(Node::Raw(token, metadata));
+            }
         }
     }
+    Ok(None)
 }
 
 #[cfg(test)]
-mod tests;
\ No newline at end of file
+mod tests;
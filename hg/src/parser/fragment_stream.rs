@@ -1,8 +1,10 @@
 use crate::lexer::Fragment;
+use crate::metadata::Location;
 
 pub struct FragmentStream<'a, I: Iterator<Item=Fragment<'a>>> {
     iter: I,
-    stashed_fragment: Option<Fragment<'a>>
+    stashed_fragment: Option<Fragment<'a>>,
+    last_end: Option<Location>,
 }
 
 impl<'a, I: Iterator<Item=Fragment<'a>>> FragmentStream<'a, I> {
@@ -11,13 +13,19 @@ impl<'a, I: Iterator<Item=Fragment<'a>>> FragmentStream<'a, I> {
         debug_assert!(self.stashed_fragment.is_none());
         self.stashed_fragment = Some(fragment);
     }
+
+    /// The end of the last token read.
+    #[inline(always)]
+    pub fn last_end(&self) -> Option<Location> {
+        self.last_end.clone()
+    }
 }
 
 impl<'a, I: Iterator<Item=Fragment<'a>>> From<I> for FragmentStream<'a, I> {
     #[inline(always)]
     fn from(iter: I) -> Self {
         Self {
-            iter, stashed_fragment: None
+            iter, stashed_fragment: None, last_end: None
         }
     }
 }
@@ -27,6 +35,10 @@ impl<'a, I: Iterator<Item=Fragment<'a>>> Iterator for FragmentStream<'a, I> {
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        self.stashed_fragment.take().or_else(|| self.iter.next())
+        let fragment = self.stashed_fragment.take().or_else(|| self.iter.next());
+        if let Some(Ok((_, metadata))) = &fragment {
+            self.last_end.clone_from(&metadata.end);
+        }
+        fragment
     }
 }
\ No newline at end of file
@@ -1,20 +1,28 @@
-use crate::metadata::{Location, Metadata};
-use crate::parser::{parse, Error};
-use crate::token::ListDelimiter::{Brace, Paren};
-use crate::token::Token::{Decimal, ExtendedSymbol, Ident, Integer, Left, Newline, Right, Symbol, Text};
+use crate::metadata::Metadata;
+use crate::parser::{parse, parse_recovering, Error, Suggestion};
+use crate::token::ListDelimiter::{Angle, Brace, Bracket, Paren};
+use crate::token::Token::{
+    Byte, Bytes, Comment, Decimal, EmbedBegin, EmbedEnd, ExtendedSymbol, Ident, Integer, InterpolationBegin, InterpolationEnd, Left,
+    Newline, Right, Symbol, Text, TextSegment,
+};
 use crate::token::{Ascii, AsciiSlice, Token};
-use crate::tree::Node::{List, Raw, Relation};
-use crate::tree::{Phrase, Verse};
-use crate::{lexer, token, verse};
+use crate::tree::Node::{Interpolation, List, Raw, Relation};
+use crate::tree::Node;
+use crate::tree::{Part, Phrase, Verse};
+use crate::lexer::{Fragment, Options, Tokeniser};
+use crate::symbols::SymbolTable;
+use crate::{token, verse};
 use std::iter::{Enumerate, Map};
 use std::vec::IntoIter;
 
-fn map_metadata(tokens: Vec<Token>) -> Map<Enumerate<IntoIter<Token>>, fn((usize, Token)) -> Result<(Token, Metadata), Box<lexer::Error>>> {
+type FragmentIter<'a> = Map<Enumerate<IntoIter<Token<'a>>>, fn((usize, Token<'a>)) -> Fragment<'a>>;
+
+fn map_metadata<'a>(tokens: Vec<Token<'a>>) -> FragmentIter<'a> {
     tokens
         .into_iter()
         .enumerate()
         .map(|(index, token)| {
-            Ok((token, Metadata {start: Some(Location { line: 1, column: index as u32 * 2 + 1}), end: Some(Location { line: 1, column: index as u32 * 2 + 2})}))
+            Ok((token, Metadata::bounds(1, index as u32 * 2 + 1, index * 2, 1, index as u32 * 2 + 2, index * 2 + 2)))
         })
 }
 
@@ -26,19 +34,24 @@ fn parse_err(tokens: Vec<Token>) -> Error {
     parse(map_metadata(tokens)).unwrap_err()
 }
 
+fn parse_recovering_ok(tokens: Vec<Token>) -> (Option<Verse>, Vec<String>) {
+    let (verse, errors) = parse_recovering(map_metadata(tokens));
+    (verse, errors.into_iter().map(|err| err.to_string()).collect())
+}
+
 #[test]
 fn flat_sequence_of_tokens() {
-    let verse = parse_ok(vec![Ident("hello".into()), Text("world".into()), Newline, Integer(42), Symbol(Ascii(b'?')), ExtendedSymbol(AsciiSlice(&[b':', b':'])), Newline]);
+    let verse = parse_ok(vec![Ident("hello".into()), Text("world".into()), Newline, Integer(42), Symbol(Ascii(b'?')), ExtendedSymbol(AsciiSlice(b"::".into())), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            Raw(Ident("hello".into()), Metadata::bounds(1, 1, 1, 2)),
-            Raw(Text("world".into()), Metadata::bounds(1, 3, 1, 4)),
-        ], Metadata::bounds(1, 1, 1, 4)),
+            Raw(Ident("hello".into()), Metadata::bounds(1, 1, 0, 1, 2, 2)),
+            Raw(Text("world".into()), Metadata::bounds(1, 3, 2, 1, 4, 4)),
+        ], Metadata::bounds(1, 1, 0, 1, 4, 4)),
         Phrase::new(vec![
-            Raw(Integer(42), Metadata::bounds(1, 7, 1, 8)),
-            Raw(Symbol(Ascii(b'?')), Metadata::bounds(1, 9, 1, 10)),
-            Raw(ExtendedSymbol(AsciiSlice(&[b':', b':'])), Metadata::bounds(1, 11, 1, 12)),
-        ], Metadata::bounds(1, 7, 1, 12))
+            Raw(Integer(42), Metadata::bounds(1, 7, 6, 1, 8, 8)),
+            Raw(Symbol(Ascii(b'?')), Metadata::bounds(1, 9, 8, 1, 10, 10)),
+            Raw(ExtendedSymbol(AsciiSlice(b"::".into())), Metadata::bounds(1, 11, 10, 1, 12, 12)),
+        ], Metadata::bounds(1, 7, 6, 1, 12, 12))
     ], verse.unwrap());
 }
 
@@ -51,13 +64,13 @@ fn empty_verse_err() {
 #[test]
 fn unterminated_phrase_err() {
     let err = parse_err(vec![Ident("hello".into()), Text("world".into())]);
-    assert_eq!("unterminated phrase", err.to_string());
+    assert_eq!("unterminated phrase at line 1, columns 1 to 4", err.to_string());
 }
 
 #[test]
 fn unexpected_token_err() {
     let err = parse_err(vec![Symbol(Ascii(b','))]);
-    assert_eq!("unexpected token Symbol(Ascii(b','))", err.to_string());
+    assert_eq!("unexpected token Symbol(Ascii(b',')) at line 1, columns 1 to 2", err.to_string());
 }
 
 #[test]
@@ -65,8 +78,8 @@ fn brace_list_empty() {
     let verse = parse_ok(vec![Left(Brace), Right(Brace), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![], Metadata::bounds(1, 1, 1, 4)),
-        ], Metadata::bounds(1, 1, 1, 4))
+            List(Brace, vec![], Metadata::bounds(1, 1, 0, 1, 4, 4)),
+        ], Metadata::bounds(1, 1, 0, 1, 4, 4))
     ], verse.unwrap());
 }
 
@@ -75,14 +88,14 @@ fn brace_list_nested_empty() {
     let verse = parse_ok(vec![Left(Brace), Left(Brace), Right(Brace), Right(Brace), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Brace, vec![
                 verse![
                     Phrase::new(vec![
-                        List(vec![], Metadata::bounds(1, 3, 1, 6))
-                    ], Metadata::bounds(1, 3, 1, 6))
+                        List(Brace, vec![], Metadata::bounds(1, 3, 2, 1, 6, 6))
+                    ], Metadata::bounds(1, 3, 2, 1, 6, 6))
                 ]
-            ], Metadata::bounds(1, 1, 1, 8)),
-        ], Metadata::bounds(1, 1, 1, 8))
+            ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8))
     ], verse.unwrap());
 }
 
@@ -91,14 +104,14 @@ fn brace_list_around_paren_list() {
     let verse = parse_ok(vec![Left(Brace), Left(Paren), Right(Paren), Right(Brace), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Brace, vec![
                 verse![
                     Phrase::new(vec![
-                        List(vec![], Metadata::bounds(1, 3, 1, 6))
-                    ], Metadata::bounds(1, 3, 1, 6))
+                        List(Paren, vec![], Metadata::bounds(1, 3, 2, 1, 6, 6))
+                    ], Metadata::bounds(1, 3, 2, 1, 6, 6))
                 ]
-            ], Metadata::bounds(1, 1, 1, 8)),
-        ], Metadata::bounds(1, 1, 1, 8))
+            ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8))
     ], verse.unwrap());
 }
 
@@ -107,16 +120,16 @@ fn brace_list_flat() {
     let verse = parse_ok(vec![Left(Brace), Ident("hello".into()), Text("world".into()), Newline, Right(Brace), Integer(42), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Brace, vec![
                 verse![
                     Phrase::new(vec![
-                        Raw(Ident("hello".into()), Metadata::bounds(1, 3, 1, 4)),
-                        Raw(Text("world".into()), Metadata::bounds(1, 5, 1, 6)),
-                    ], Metadata::bounds(1, 3, 1, 6))
+                        Raw(Ident("hello".into()), Metadata::bounds(1, 3, 2, 1, 4, 4)),
+                        Raw(Text("world".into()), Metadata::bounds(1, 5, 4, 1, 6, 6)),
+                    ], Metadata::bounds(1, 3, 2, 1, 6, 6))
                 ]
-            ], Metadata::bounds(1, 1, 1, 10)),
-            Raw(Integer(42), Metadata::bounds(1, 11, 1, 12)),
-        ], Metadata::bounds(1, 1, 1, 12)),
+            ], Metadata::bounds(1, 1, 0, 1, 10, 10)),
+            Raw(Integer(42), Metadata::bounds(1, 11, 10, 1, 12, 12)),
+        ], Metadata::bounds(1, 1, 0, 1, 12, 12)),
     ], verse.unwrap());
 }
 
@@ -125,37 +138,38 @@ fn brace_list_nested() {
     let verse = parse_ok(vec![Left(Brace), Ident("hello".into()), Left(Brace), Text("world".into()), Newline, Right(Brace), Right(Brace), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Brace, vec![
                 verse![
                     Phrase::new(vec![
-                        Raw(Ident("hello".into()), Metadata::bounds(1, 3, 1, 4)),
+                        Raw(Ident("hello".into()), Metadata::bounds(1, 3, 2, 1, 4, 4)),
                         List(
+                            Brace,
                             vec![
                                 verse![
                                     Phrase::new(vec![
-                                        Raw(Text("world".into()), Metadata::bounds(1, 7, 1, 8)),
-                                    ], Metadata::bounds(1, 7, 1, 8))
+                                        Raw(Text("world".into()), Metadata::bounds(1, 7, 6, 1, 8, 8)),
+                                    ], Metadata::bounds(1, 7, 6, 1, 8, 8))
                                 ]
                             ],
-                            Metadata::bounds(1, 5, 1, 12)
+                            Metadata::bounds(1, 5, 4, 1, 12, 12)
                         )
-                    ], Metadata::bounds(1, 3, 1, 12))
+                    ], Metadata::bounds(1, 3, 2, 1, 12, 12))
                 ]
-            ], Metadata::bounds(1, 1, 1, 14)),
-        ], Metadata::bounds(1, 1, 1, 14))
+            ], Metadata::bounds(1, 1, 0, 1, 14, 14)),
+        ], Metadata::bounds(1, 1, 0, 1, 14, 14))
     ], verse.unwrap());
 }
 
 #[test]
 fn brace_list_unterminated_err() {
     let err = parse_err(vec![Left(Brace), Ident("hello".into()), Newline]);
-    assert_eq!("unterminated list", err.to_string());
+    assert_eq!("unterminated list at line 1, columns 1 to 2", err.to_string());
 }
 
 #[test]
 fn brace_list_expected_token_err() {
     let err = parse_err(vec![Left(Brace), Ident("hello".into()), Right(Paren)]);
-    assert_eq!("unexpected token Right(Paren)", err.to_string());
+    assert_eq!("mismatched closing delimiter ')' at line 1, columns 5 to 6 for '{' at line 1, columns 1 to 2; perhaps it should be '}'", err.to_string());
 }
 
 #[test]
@@ -163,8 +177,8 @@ fn paren_list_empty() {
     let verse = parse_ok(vec![Left(Paren), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![], Metadata::bounds(1, 1, 1, 4)),
-        ], Metadata::bounds(1, 1, 1, 4))
+            List(Paren, vec![], Metadata::bounds(1, 1, 0, 1, 4, 4)),
+        ], Metadata::bounds(1, 1, 0, 1, 4, 4))
     ], verse.unwrap());
 }
 
@@ -173,12 +187,12 @@ fn paren_list_nested_empty() {
     let verse = parse_ok(vec![Left(Paren), Left(Paren), Right(Paren), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Paren, vec![
                 verse![
-                    Phrase::new(vec![List(vec![], Metadata::bounds(1, 3, 1, 6))], Metadata::bounds(1, 3, 1, 6))
+                    Phrase::new(vec![List(Paren, vec![], Metadata::bounds(1, 3, 2, 1, 6, 6))], Metadata::bounds(1, 3, 2, 1, 6, 6))
                 ]
-            ], Metadata::bounds(1, 1, 1, 8)),
-        ], Metadata::bounds(1, 1, 1, 8))
+            ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8))
     ], verse.unwrap());
 }
 
@@ -187,12 +201,12 @@ fn paren_list_around_brace_list() {
     let verse = parse_ok(vec![Left(Paren), Left(Brace), Right(Brace), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Paren, vec![
                 verse![
-                    Phrase::new(vec![List(vec![], Metadata::bounds(1, 3, 1, 6))], Metadata::bounds(1, 3, 1, 6))
+                    Phrase::new(vec![List(Brace, vec![], Metadata::bounds(1, 3, 2, 1, 6, 6))], Metadata::bounds(1, 3, 2, 1, 6, 6))
                 ]
-            ], Metadata::bounds(1, 1, 1, 8)),
-        ], Metadata::bounds(1, 1, 1, 8))
+            ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8))
     ], verse.unwrap());
 }
 
@@ -201,14 +215,14 @@ fn paren_list_with_one_verse_and_phrase_with_one_node() {
     let verse = parse_ok(vec![Left(Paren), Integer(1), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Paren, vec![
                 verse![
                     Phrase::new(vec![
-                        Raw(Integer(1), Metadata::bounds(1, 3, 1, 4))
-                    ], Metadata::bounds(1, 3, 1, 4)) 
+                        Raw(Integer(1), Metadata::bounds(1, 3, 2, 1, 4, 4))
+                    ], Metadata::bounds(1, 3, 2, 1, 4, 4)) 
                 ]
-            ], Metadata::bounds(1, 1, 1, 6)),
-        ], Metadata::bounds(1, 1, 1, 6))
+            ], Metadata::bounds(1, 1, 0, 1, 6, 6)),
+        ], Metadata::bounds(1, 1, 0, 1, 6, 6))
     ], verse.unwrap());
 }
 
@@ -217,12 +231,12 @@ fn paren_list_with_one_verse_trailing_comma() {
     let verse = parse_ok(vec![Left(Paren), Integer(1), Symbol(Ascii(b',')), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Paren, vec![
                 verse![
-                    Phrase::new(vec![Raw(Integer(1), Metadata::bounds(1, 3, 1, 4))], Metadata::bounds(1, 3, 1, 4))
+                    Phrase::new(vec![Raw(Integer(1), Metadata::bounds(1, 3, 2, 1, 4, 4))], Metadata::bounds(1, 3, 2, 1, 4, 4))
                 ]
-            ], Metadata::bounds(1, 1, 1, 8)),
-        ], Metadata::bounds(1, 1, 1, 8))
+            ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8))
     ], verse.unwrap());
 }
 
@@ -231,12 +245,12 @@ fn paren_list_with_one_verse_and_phrase_with_many_nodes() {
     let verse = parse_ok(vec![Left(Paren), Integer(1), Integer(2), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Paren, vec![
                 verse![
-                    Phrase::new(vec![Raw(Integer(1), Metadata::bounds(1, 3, 1, 4)), Raw(Integer(2), Metadata::bounds(1, 5, 1, 6))], Metadata::bounds(1, 3, 1, 6))
+                    Phrase::new(vec![Raw(Integer(1), Metadata::bounds(1, 3, 2, 1, 4, 4)), Raw(Integer(2), Metadata::bounds(1, 5, 4, 1, 6, 6))], Metadata::bounds(1, 3, 2, 1, 6, 6))
                 ]
-            ], Metadata::bounds(1, 1, 1, 8)),
-        ], Metadata::bounds(1, 1, 1, 8))
+            ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8))
     ], verse.unwrap());
 }
 
@@ -245,34 +259,71 @@ fn paren_list_with_many_verses() {
     let verse = parse_ok(vec![Left(Paren), Integer(1), Integer(2), Symbol(Ascii(b',')), Integer(3), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Paren, vec![
                 verse![
-                    Phrase::new(vec![Raw(Integer(1), Metadata::bounds(1, 3, 1, 4)), Raw(Integer(2), Metadata::bounds(1, 5, 1, 6))], Metadata::bounds(1, 3, 1, 6)),
+                    Phrase::new(vec![Raw(Integer(1), Metadata::bounds(1, 3, 2, 1, 4, 4)), Raw(Integer(2), Metadata::bounds(1, 5, 4, 1, 6, 6))], Metadata::bounds(1, 3, 2, 1, 6, 6)),
                 ],
                 verse![
-                    Phrase::new(vec![Raw(Integer(3), Metadata::bounds(1, 9, 1, 10))], Metadata::bounds(1, 9, 1, 10))
+                    Phrase::new(vec![Raw(Integer(3), Metadata::bounds(1, 9, 8, 1, 10, 10))], Metadata::bounds(1, 9, 8, 1, 10, 10))
                 ]
-            ], Metadata::bounds(1, 1, 1, 12)),
-        ], Metadata::bounds(1, 1, 1, 12))
+            ], Metadata::bounds(1, 1, 0, 1, 12, 12)),
+        ], Metadata::bounds(1, 1, 0, 1, 12, 12))
     ], verse.unwrap());
 }
 
 #[test]
 fn list_empty_verse_err() {
     let err = parse_err(vec![Left(Paren), Integer(1), Symbol(Ascii(b',')), Newline, Newline, Symbol(Ascii(b',')), Right(Paren)]);
-    assert_eq!("empty verse", err.to_string());
+    assert_eq!("empty verse at line 1, columns 11 to 12", err.to_string());
 }
 
 #[test]
 fn list_unterminated_err() {
     let err = parse_err(vec![Left(Paren), Ident("hello".into()), Newline]);
-    assert_eq!("unterminated list", err.to_string());
+    assert_eq!("unterminated list at line 1, columns 1 to 2", err.to_string());
 }
 
 #[test]
 fn paren_list_expected_brace_token_err() {
     let err = parse_err(vec![Left(Paren), Ident("hello".into()), Right(Brace)]);
-    assert_eq!("unexpected token Right(Brace)", err.to_string());
+    assert_eq!("mismatched closing delimiter '}' at line 1, columns 5 to 6 for '(' at line 1, columns 1 to 2; perhaps it should be ')'", err.to_string());
+}
+
+#[test]
+fn angle_list_with_many_verses() {
+    let verse = parse_ok(vec![Ident("Map".into()), Left(Angle), Ident("K".into()), Symbol(Ascii(b',')), Ident("V".into()), Right(Angle), Newline]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Raw(Ident("Map".into()), Metadata::bounds(1, 1, 0, 1, 2, 2)),
+            List(Angle, vec![
+                verse![
+                    Phrase::new(vec![Raw(Ident("K".into()), Metadata::bounds(1, 5, 4, 1, 6, 6))], Metadata::bounds(1, 5, 4, 1, 6, 6))
+                ],
+                verse![
+                    Phrase::new(vec![Raw(Ident("V".into()), Metadata::bounds(1, 9, 8, 1, 10, 10))], Metadata::bounds(1, 9, 8, 1, 10, 10))
+                ]
+            ], Metadata::bounds(1, 3, 2, 1, 12, 12)),
+        ], Metadata::bounds(1, 1, 0, 1, 12, 12))
+    ], verse.unwrap());
+}
+
+#[test]
+fn angle_list_expected_paren_token_err() {
+    let err = parse_err(vec![Left(Angle), Ident("T".into()), Right(Paren)]);
+    assert_eq!("mismatched closing delimiter ')' at line 1, columns 5 to 6 for '<' at line 1, columns 1 to 2; perhaps it should be '>'", err.to_string());
+}
+
+#[test]
+fn paren_list_expected_angle_token_err() {
+    let err = parse_err(vec![Left(Paren), Ident("T".into()), Right(Angle)]);
+    assert_eq!("mismatched closing delimiter '>' at line 1, columns 5 to 6 for '(' at line 1, columns 1 to 2; perhaps it should be ')'", err.to_string());
+}
+
+#[test]
+fn mismatched_delimiter_suggests_missing_closer() {
+    let err = parse_err(vec![Left(Bracket), Left(Paren), Ident("a".into()), Right(Bracket)]);
+    assert!(matches!(err, Error::MismatchedDelimiter(Paren, _, Bracket, _, Suggestion::Insert(Paren))), "{err:?}");
+    assert_eq!("mismatched closing delimiter ']' at line 1, columns 7 to 8 for '(' at line 1, columns 3 to 4; perhaps a ')' is missing", err.to_string());
 }
 
 #[test]
@@ -281,11 +332,11 @@ fn relation_single() {
     assert_eq!(verse![
         Phrase::new(vec![
             Relation(
-                Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 1, 2))), 
-                Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 5, 1, 6))], Metadata::bounds(1, 5, 1, 6)), 
-                Metadata::bounds(1, 1, 1, 6)
+                Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 0, 1, 2, 2))), 
+                Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 5, 4, 1, 6, 6))], Metadata::bounds(1, 5, 4, 1, 6, 6)), 
+                Metadata::bounds(1, 1, 0, 1, 6, 6)
             ),
-        ], Metadata::bounds(1, 1, 1, 6))
+        ], Metadata::bounds(1, 1, 0, 1, 6, 6))
     ], verse.unwrap());
 }
 
@@ -295,11 +346,11 @@ fn relation_single_long_tail() {
     assert_eq!(verse![
         Phrase::new(vec![
             Relation(
-                Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 1, 2))), 
-                Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 5, 1, 6)), Raw(Integer(3), Metadata::bounds(1, 7, 1, 8))], Metadata::bounds(1, 5, 1, 8)), 
-                Metadata::bounds(1, 1, 1, 8)
+                Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 0, 1, 2, 2))), 
+                Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 5, 4, 1, 6, 6)), Raw(Integer(3), Metadata::bounds(1, 7, 6, 1, 8, 8))], Metadata::bounds(1, 5, 4, 1, 8, 8)), 
+                Metadata::bounds(1, 1, 0, 1, 8, 8)
             ),
-        ], Metadata::bounds(1, 1, 1, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
     ], verse.unwrap());
 }
 
@@ -311,14 +362,14 @@ fn relation_multiple() {
             Relation(
                 Box::new(
                     Relation(
-                        Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 1, 2))), 
-                        Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 5, 1, 6)), Raw(Integer(3), Metadata::bounds(1, 7, 1, 8))], Metadata::bounds(1, 5, 1, 8)), 
-                        Metadata::bounds(1, 1, 1, 8))
+                        Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 0, 1, 2, 2))), 
+                        Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 5, 4, 1, 6, 6)), Raw(Integer(3), Metadata::bounds(1, 7, 6, 1, 8, 8))], Metadata::bounds(1, 5, 4, 1, 8, 8)), 
+                        Metadata::bounds(1, 1, 0, 1, 8, 8))
                     ), 
-                Phrase::new(vec![Raw(Integer(4), Metadata::bounds(1, 11, 1, 12))], Metadata::bounds(1, 11, 1, 12)), 
-                Metadata::bounds(1, 1, 1, 12)
+                Phrase::new(vec![Raw(Integer(4), Metadata::bounds(1, 11, 10, 1, 12, 12))], Metadata::bounds(1, 11, 10, 1, 12, 12)), 
+                Metadata::bounds(1, 1, 0, 1, 12, 12)
             ),
-        ], Metadata::bounds(1, 1, 1, 12)),
+        ], Metadata::bounds(1, 1, 0, 1, 12, 12)),
     ], verse.unwrap());
 }
 
@@ -328,22 +379,22 @@ fn relation_with_list_tail() {
     assert_eq!(verse![
         Phrase::new(vec![
             Relation(
-                Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 1, 2))), 
+                Box::new(Raw(Integer(1), Metadata::bounds(1, 1, 0, 1, 2, 2))), 
                 Phrase::new(vec![
-                    List(vec![
+                    List(Brace, vec![
                         verse![
                             Phrase::new(vec![
-                                Raw(Integer(2), Metadata::bounds(1, 7, 1, 8))
-                            ], Metadata::bounds(1, 7, 1, 8)),
+                                Raw(Integer(2), Metadata::bounds(1, 7, 6, 1, 8, 8))
+                            ], Metadata::bounds(1, 7, 6, 1, 8, 8)),
                             Phrase::new(vec![
-                                Raw(Integer(3), Metadata::bounds(1, 11, 1, 12))
-                            ], Metadata::bounds(1, 11, 1, 12))
+                                Raw(Integer(3), Metadata::bounds(1, 11, 10, 1, 12, 12))
+                            ], Metadata::bounds(1, 11, 10, 1, 12, 12))
                         ]
-                    ], Metadata::bounds(1, 5, 1, 14))
-                ], Metadata::bounds(1, 5, 1, 14)), 
-                Metadata::bounds(1, 1, 1, 14)
+                    ], Metadata::bounds(1, 5, 4, 1, 14, 14))
+                ], Metadata::bounds(1, 5, 4, 1, 14, 14)), 
+                Metadata::bounds(1, 1, 0, 1, 14, 14)
             ),
-        ], Metadata::bounds(1, 1, 1, 14)),
+        ], Metadata::bounds(1, 1, 0, 1, 14, 14)),
     ], verse.unwrap());
 }
 
@@ -352,24 +403,24 @@ fn relation_inside_brace_list() {
     let verse = parse_ok(vec![Left(Brace), Integer(1), Symbol(Ascii(b':')), Integer(2), Integer(3), Symbol(Ascii(b':')), Integer(4), Right(Brace), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Brace, vec![
                 verse![
                     Phrase::new(vec![
                         Relation(
                             Box::new(
                                 Relation(
-                                    Box::new(Raw(Integer(1), Metadata::bounds(1, 3, 1, 4))), 
-                                    Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 7, 1, 8)), Raw(Integer(3), Metadata::bounds(1, 9, 1, 10))], Metadata::bounds(1, 7, 1, 10)), 
-                                    Metadata::bounds(1, 3, 1, 10)
+                                    Box::new(Raw(Integer(1), Metadata::bounds(1, 3, 2, 1, 4, 4))), 
+                                    Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 7, 6, 1, 8, 8)), Raw(Integer(3), Metadata::bounds(1, 9, 8, 1, 10, 10))], Metadata::bounds(1, 7, 6, 1, 10, 10)), 
+                                    Metadata::bounds(1, 3, 2, 1, 10, 10)
                                 )
                             ), 
-                            Phrase::new(vec![Raw(Integer(4), Metadata::bounds(1, 13, 1, 14))], Metadata::bounds(1, 13, 1, 14)), 
-                            Metadata::bounds(1, 3, 1, 14)
+                            Phrase::new(vec![Raw(Integer(4), Metadata::bounds(1, 13, 12, 1, 14, 14))], Metadata::bounds(1, 13, 12, 1, 14, 14)), 
+                            Metadata::bounds(1, 3, 2, 1, 14, 14)
                         ),
-                    ], Metadata::bounds(1, 3, 1, 14))
+                    ], Metadata::bounds(1, 3, 2, 1, 14, 14))
                 ]
-            ], Metadata::bounds(1, 1, 1, 16))
-        ], Metadata::bounds(1, 1, 1, 16))
+            ], Metadata::bounds(1, 1, 0, 1, 16, 16))
+        ], Metadata::bounds(1, 1, 0, 1, 16, 16))
     ], verse.unwrap());
 }
 
@@ -378,49 +429,49 @@ fn relation_inside_list() {
     let verse = parse_ok(vec![Left(Paren), Integer(1), Symbol(Ascii(b':')), Integer(2), Integer(3), Symbol(Ascii(b':')), Integer(4), Right(Paren), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            List(vec![
+            List(Paren, vec![
                 verse![
                     Phrase::new(vec![
                         Relation(
                             Box::new(
                                 Relation(
-                                    Box::new(Raw(Integer(1), Metadata::bounds(1, 3, 1, 4))), 
-                                    Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 7, 1, 8)), Raw(Integer(3), Metadata::bounds(1, 9, 1, 10))], Metadata::bounds(1, 7, 1, 10)), 
-                                    Metadata::bounds(1, 3, 1, 10)
+                                    Box::new(Raw(Integer(1), Metadata::bounds(1, 3, 2, 1, 4, 4))), 
+                                    Phrase::new(vec![Raw(Integer(2), Metadata::bounds(1, 7, 6, 1, 8, 8)), Raw(Integer(3), Metadata::bounds(1, 9, 8, 1, 10, 10))], Metadata::bounds(1, 7, 6, 1, 10, 10)), 
+                                    Metadata::bounds(1, 3, 2, 1, 10, 10)
                                 )
                             ), 
-                            Phrase::new(vec![Raw(Integer(4), Metadata::bounds(1, 13, 1, 14))], Metadata::bounds(1, 13, 1, 14)), 
-                            Metadata::bounds(1, 3, 1, 14)
+                            Phrase::new(vec![Raw(Integer(4), Metadata::bounds(1, 13, 12, 1, 14, 14))], Metadata::bounds(1, 13, 12, 1, 14, 14)), 
+                            Metadata::bounds(1, 3, 2, 1, 14, 14)
                         ),
-                    ], Metadata::bounds(1, 3, 1, 14))
+                    ], Metadata::bounds(1, 3, 2, 1, 14, 14))
                 ]
-            ], Metadata::bounds(1, 1, 1, 16))
-        ], Metadata::bounds(1, 1, 1, 16))
+            ], Metadata::bounds(1, 1, 0, 1, 16, 16))
+        ], Metadata::bounds(1, 1, 0, 1, 16, 16))
     ], verse.unwrap());
 }
 
 #[test]
 fn relation_empty_starting_segment_err() {
     let err = parse_err(vec![Symbol(Ascii(b':')), Integer(2)]);
-    assert_eq!("empty relation segment", err.to_string());
+    assert_eq!("empty relation segment at line 1, columns 1 to 2", err.to_string());
 }
 
 #[test]
 fn relation_empty_intermediate_segment_err() {
     let err = parse_err(vec![Integer(1), Symbol(Ascii(b':')), Integer(2), Symbol(Ascii(b':')), Symbol(Ascii(b':'))]);
-    assert_eq!("empty relation segment", err.to_string());
+    assert_eq!("empty relation segment at line 1, columns 7 to 8", err.to_string());
 }
 
 #[test]
 fn relation_multiple_trailing_empty_segment_err() {
     let err = parse_err(vec![Integer(1), Symbol(Ascii(b':')), Integer(2), Integer(3), Symbol(Ascii(b':')), Integer(4), Symbol(Ascii(b':')), Newline]);
-    assert_eq!("empty relation segment", err.to_string());
+    assert_eq!("empty relation segment at line 1, columns 13 to 14", err.to_string());
 }
 
 #[test]
 fn relation_unterminated_err() {
     let err = parse_err(vec![Integer(1), Symbol(Ascii(b':')), Integer(2)]);
-    assert_eq!("unterminated relation", err.to_string());
+    assert_eq!("unterminated relation at line 1, columns 3 to 4", err.to_string());
 }
 
 #[test]
@@ -428,9 +479,9 @@ fn negative_integer() {
     let verse = parse_ok(vec![Symbol(Ascii(b'-')), Integer(1), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![
-            Raw(Symbol(Ascii(b'-')), Metadata::bounds(1, 1, 1, 2)), 
-            Raw(Integer(1), Metadata::bounds(1, 3, 1, 4)), 
-        ], Metadata::bounds(1, 1, 1, 4))
+            Raw(Symbol(Ascii(b'-')), Metadata::bounds(1, 1, 0, 1, 2, 2)), 
+            Raw(Integer(1), Metadata::bounds(1, 3, 2, 1, 4, 4)), 
+        ], Metadata::bounds(1, 1, 0, 1, 4, 4))
     ], verse.unwrap());
 }
 
@@ -439,8 +490,255 @@ fn negative_decimal() {
     let verse = parse_ok(vec![Symbol(Ascii(b'-')), Decimal(token::Decimal(10, 5, 2)), Newline]);
     assert_eq!(verse![
         Phrase::new(vec![  
-            Raw(Symbol(Ascii(b'-')), Metadata::bounds(1, 1, 1, 2)), 
-            Raw(Decimal(token::Decimal(10, 5, 2)), Metadata::bounds(1, 3, 1, 4)), 
-        ], Metadata::bounds(1, 1, 1, 4))
+            Raw(Symbol(Ascii(b'-')), Metadata::bounds(1, 1, 0, 1, 2, 2)), 
+            Raw(Decimal(token::Decimal(10, 5, 2)), Metadata::bounds(1, 3, 2, 1, 4, 4)), 
+        ], Metadata::bounds(1, 1, 0, 1, 4, 4))
+    ], verse.unwrap());
+}
+#[test]
+fn bytes_and_byte() {
+    let verse = parse_ok(vec![Bytes(b"\x00\xff".into()), Byte(b'a'), Newline]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Raw(Bytes(b"\x00\xff".into()), Metadata::bounds(1, 1, 0, 1, 2, 2)),
+            Raw(Byte(b'a'), Metadata::bounds(1, 3, 2, 1, 4, 4)),
+        ], Metadata::bounds(1, 1, 0, 1, 4, 4))
     ], verse.unwrap());
-}
\ No newline at end of file
+}
+
+#[test]
+fn comments_ignored() {
+    let verse = parse_ok(vec![Comment("// one".into()), Newline, Integer(1), Comment("/* two */".into()), Integer(2), Newline]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Raw(Integer(1), Metadata::bounds(1, 5, 4, 1, 6, 6)),
+            Raw(Integer(2), Metadata::bounds(1, 9, 8, 1, 10, 10)),
+        ], Metadata::bounds(1, 5, 4, 1, 10, 10))
+    ], verse.unwrap());
+}
+
+#[test]
+fn interpolation() {
+    let verse = parse_ok(vec![
+        InterpolationBegin, TextSegment("Hello ".into()), EmbedBegin, Ident("user".into()), EmbedEnd, TextSegment("!".into()), InterpolationEnd, Newline
+    ]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Interpolation(vec![
+                Part::Text("Hello ".into(), Metadata::bounds(1, 3, 2, 1, 4, 4)),
+                Part::Expression(Phrase::new(vec![
+                    Raw(Ident("user".into()), Metadata::bounds(1, 7, 6, 1, 8, 8)),
+                ], Metadata::bounds(1, 7, 6, 1, 8, 8))),
+                Part::Text("!".into(), Metadata::bounds(1, 11, 10, 1, 12, 12)),
+            ], Metadata::bounds(1, 1, 0, 1, 14, 14)),
+        ], Metadata::bounds(1, 1, 0, 1, 14, 14))
+    ], verse.unwrap());
+}
+
+#[test]
+fn interpolation_with_relation_and_nested() {
+    let verse = parse_ok(vec![
+        InterpolationBegin, EmbedBegin, Ident("a".into()), Symbol(Ascii(b':')), InterpolationBegin, EmbedBegin, Integer(1), EmbedEnd, InterpolationEnd, EmbedEnd, InterpolationEnd, Newline
+    ]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Interpolation(vec![
+                Part::Expression(Phrase::new(vec![
+                    Relation(
+                        Box::new(Raw(Ident("a".into()), Metadata::bounds(1, 5, 4, 1, 6, 6))),
+                        Phrase::new(vec![
+                            Interpolation(vec![
+                                Part::Expression(Phrase::new(vec![
+                                    Raw(Integer(1), Metadata::bounds(1, 13, 12, 1, 14, 14)),
+                                ], Metadata::bounds(1, 13, 12, 1, 14, 14))),
+                            ], Metadata::bounds(1, 9, 8, 1, 18, 18)),
+                        ], Metadata::bounds(1, 9, 8, 1, 18, 18)),
+                        Metadata::bounds(1, 5, 4, 1, 18, 18)
+                    ),
+                ], Metadata::bounds(1, 5, 4, 1, 18, 18))),
+            ], Metadata::bounds(1, 1, 0, 1, 22, 22)),
+        ], Metadata::bounds(1, 1, 0, 1, 22, 22))
+    ], verse.unwrap());
+}
+
+#[test]
+fn interpolation_errs() {
+    for (tokens, expect) in [
+        (vec![InterpolationBegin, EmbedBegin, EmbedEnd, InterpolationEnd, Newline], "empty embedded expression at line 1, columns 3 to 6"),
+        (vec![InterpolationBegin, TextSegment("a".into())], "unterminated interpolation at line 1, columns 1 to 2"),
+        (vec![InterpolationBegin, EmbedBegin, Ident("a".into())], "unterminated interpolation at line 1, columns 1 to 2"),
+        (vec![InterpolationBegin, EmbedBegin, Ident("a".into()), Symbol(Ascii(b',')), EmbedEnd, InterpolationEnd], "unexpected token Symbol(Ascii(b',')) at line 1, columns 7 to 8"),
+        (vec![EmbedEnd], "unexpected token EmbedEnd at line 1, columns 1 to 2"),
+    ] {
+        assert_eq!(expect, parse_err(tokens).to_string());
+    }
+}
+
+#[test]
+fn recovering_skips_stray_tokens() {
+    let (verse, errors) = parse_recovering_ok(vec![Ident("a".into()), Right(Paren), Symbol(Ascii(b',')), Ident("b".into()), Newline]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Raw(Ident("a".into()), Metadata::bounds(1, 1, 0, 1, 2, 2)),
+            Raw(Ident("b".into()), Metadata::bounds(1, 7, 6, 1, 8, 8)),
+        ], Metadata::bounds(1, 1, 0, 1, 8, 8))
+    ], verse.unwrap());
+    assert_eq!(vec!["unexpected token Right(Paren) at line 1, columns 3 to 4", "unexpected token Symbol(Ascii(b',')) at line 1, columns 5 to 6"], errors);
+}
+
+#[test]
+fn recovering_unterminated_list() {
+    let (verse, errors) = parse_recovering_ok(vec![Ident("a".into()), Newline, Left(Bracket), Integer(1), Symbol(Ascii(b',')), Newline]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Raw(Ident("a".into()), Metadata::bounds(1, 1, 0, 1, 2, 2)),
+        ], Metadata::bounds(1, 1, 0, 1, 2, 2)),
+        Phrase::new(vec![
+            List(Bracket, vec![
+                verse![Phrase::new(vec![Raw(Integer(1), Metadata::bounds(1, 7, 6, 1, 8, 8))], Metadata::bounds(1, 7, 6, 1, 8, 8))],
+                verse![Phrase::new(vec![Node::Error(Metadata::bounds(1, 12, 12, 1, 12, 12))], Metadata::bounds(1, 12, 12, 1, 12, 12))],
+            ], Metadata::bounds(1, 5, 4, 1, 12, 12)),
+        ], Metadata::bounds(1, 5, 4, 1, 12, 12))
+    ], verse.unwrap());
+    assert_eq!(vec!["unterminated list at line 1, columns 5 to 6"], errors);
+}
+
+#[test]
+fn recovering_unterminated_nested_lists() {
+    let (verse, errors) = parse_recovering(Tokeniser::new("(a, (b, c", SymbolTable::default()));
+    let missing = Metadata::bounds(2, 0, 9, 2, 0, 9);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            List(Paren, vec![
+                verse![Phrase::new(vec![Raw(Ident("a".into()), Metadata::bounds(1, 2, 1, 1, 2, 2))], Metadata::bounds(1, 2, 1, 1, 2, 2))],
+                verse![Phrase::new(vec![
+                    List(Paren, vec![
+                        verse![Phrase::new(vec![Raw(Ident("b".into()), Metadata::bounds(1, 6, 5, 1, 6, 6))], Metadata::bounds(1, 6, 5, 1, 6, 6))],
+                        verse![
+                            Phrase::new(vec![Raw(Ident("c".into()), Metadata::bounds(1, 9, 8, 1, 9, 9))], Metadata::bounds(1, 9, 8, 1, 9, 9)),
+                            Phrase::new(vec![Node::Error(missing.clone())], missing.clone()),
+                        ],
+                    ], Metadata::bounds(1, 5, 4, 2, 0, 9)),
+                    Node::Error(missing.clone()),
+                ], Metadata::bounds(1, 5, 4, 2, 0, 9))],
+            ], Metadata::bounds(1, 1, 0, 2, 0, 9)),
+        ], Metadata::bounds(1, 1, 0, 2, 0, 9))
+    ], verse.unwrap());
+    assert_eq!(
+        vec!["unterminated list at line 1, column 5", "unterminated list at line 1, column 1"],
+        errors.into_iter().map(|err| err.to_string()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn recovering_unterminated_relation() {
+    let (verse, errors) = parse_recovering(Tokeniser::new("a: b: (c", SymbolTable::default()));
+    let missing = Metadata::bounds(2, 0, 8, 2, 0, 8);
+    let list = List(Paren, vec![
+        verse![
+            Phrase::new(vec![Raw(Ident("c".into()), Metadata::bounds(1, 8, 7, 1, 8, 8))], Metadata::bounds(1, 8, 7, 1, 8, 8)),
+            Phrase::new(vec![Node::Error(missing.clone())], missing),
+        ],
+    ], Metadata::bounds(1, 7, 6, 2, 0, 8));
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Relation(
+                Box::new(Relation(
+                    Box::new(Raw(Ident("a".into()), Metadata::bounds(1, 1, 0, 1, 1, 1))),
+                    Phrase::new(vec![Raw(Ident("b".into()), Metadata::bounds(1, 4, 3, 1, 4, 4))], Metadata::bounds(1, 4, 3, 1, 4, 4)),
+                    Metadata::bounds(1, 1, 0, 1, 4, 4)
+                )),
+                Phrase::new(vec![list], Metadata::bounds(1, 7, 6, 2, 0, 8)),
+                Metadata::bounds(1, 1, 0, 2, 0, 8)
+            ),
+        ], Metadata::bounds(1, 1, 0, 2, 0, 8))
+    ], verse.unwrap());
+    assert_eq!(
+        vec!["unterminated list at line 1, column 7", "unterminated relation at line 1, column 5"],
+        errors.into_iter().map(|err| err.to_string()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn recovering_mismatched_delimiters() {
+    // [(a] (b} c
+    let (verse, errors) = parse_recovering_ok(vec![
+        Left(Bracket), Left(Paren), Ident("a".into()), Right(Bracket), Left(Paren), Ident("b".into()), Right(Brace), Ident("c".into()), Newline
+    ]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            List(Bracket, vec![
+                verse![Phrase::new(vec![
+                    List(Paren, vec![
+                        verse![Phrase::new(vec![Raw(Ident("a".into()), Metadata::bounds(1, 5, 4, 1, 6, 6))], Metadata::bounds(1, 5, 4, 1, 6, 6))]
+                    ], Metadata::bounds(1, 3, 2, 1, 6, 6)),
+                ], Metadata::bounds(1, 3, 2, 1, 6, 6))]
+            ], Metadata::bounds(1, 1, 0, 1, 8, 8)),
+            List(Paren, vec![
+                verse![Phrase::new(vec![Raw(Ident("b".into()), Metadata::bounds(1, 11, 10, 1, 12, 12))], Metadata::bounds(1, 11, 10, 1, 12, 12))]
+            ], Metadata::bounds(1, 9, 8, 1, 14, 14)),
+            Raw(Ident("c".into()), Metadata::bounds(1, 15, 14, 1, 16, 16)),
+        ], Metadata::bounds(1, 1, 0, 1, 16, 16))
+    ], verse.unwrap());
+    assert_eq!(vec![
+        "mismatched closing delimiter ']' at line 1, columns 7 to 8 for '(' at line 1, columns 3 to 4; perhaps a ')' is missing",
+        "mismatched closing delimiter '}' at line 1, columns 13 to 14 for '(' at line 1, columns 9 to 10; perhaps it should be ')'",
+    ], errors);
+}
+
+#[test]
+fn recovering_empty_relation_segments() {
+    let (verse, errors) = parse_recovering_ok(vec![Symbol(Ascii(b':')), Ident("b".into()), Newline, Ident("c".into()), Symbol(Ascii(b':')), Newline]);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Relation(
+                Box::new(Node::Error(Metadata::bounds(1, 1, 0, 1, 2, 2))),
+                Phrase::new(vec![
+                    Raw(Ident("b".into()), Metadata::bounds(1, 3, 2, 1, 4, 4)),
+                ], Metadata::bounds(1, 3, 2, 1, 4, 4)),
+                Metadata::bounds(1, 1, 0, 1, 4, 4)
+            ),
+        ], Metadata::bounds(1, 1, 0, 1, 4, 4)),
+        Phrase::new(vec![
+            Relation(
+                Box::new(Raw(Ident("c".into()), Metadata::bounds(1, 7, 6, 1, 8, 8))),
+                Phrase::new(vec![
+                    Node::Error(Metadata::bounds(1, 11, 10, 1, 11, 10)),
+                ], Metadata::bounds(1, 11, 10, 1, 11, 10)),
+                Metadata::bounds(1, 7, 6, 1, 11, 10)
+            ),
+        ], Metadata::bounds(1, 7, 6, 1, 11, 10))
+    ], verse.unwrap());
+    assert_eq!(vec!["empty relation segment at line 1, columns 1 to 2", "empty relation segment at line 1, columns 9 to 10"], errors);
+}
+
+#[test]
+fn recovering_with_lexer_errors() {
+    let options = Options {
+        recover: true,
+        ..Options::default()
+    };
+    let tokeniser = Tokeniser::with_options("a \\ b\n[c, d\n", SymbolTable::default(), options);
+    let (verse, errors) = parse_recovering(tokeniser);
+    assert_eq!(verse![
+        Phrase::new(vec![
+            Raw(Ident("a".into()), Metadata::bounds(1, 1, 0, 1, 1, 1)),
+            Raw(Ident("b".into()), Metadata::bounds(1, 5, 4, 1, 5, 5)),
+        ], Metadata::bounds(1, 1, 0, 1, 5, 5)),
+        Phrase::new(vec![
+            List(Bracket, vec![
+                verse![Phrase::new(vec![Raw(Ident("c".into()), Metadata::bounds(2, 2, 7, 2, 2, 8))], Metadata::bounds(2, 2, 7, 2, 2, 8))],
+                verse![
+                    Phrase::new(vec![Raw(Ident("d".into()), Metadata::bounds(2, 5, 10, 2, 5, 11))], Metadata::bounds(2, 5, 10, 2, 5, 11)),
+                    Phrase::new(vec![Node::Error(Metadata::bounds(3, 0, 12, 3, 0, 12))], Metadata::bounds(3, 0, 12, 3, 0, 12)),
+                ],
+            ], Metadata::bounds(2, 1, 6, 3, 0, 12)),
+        ], Metadata::bounds(2, 1, 6, 3, 0, 12))
+    ], verse.unwrap());
+    assert_eq!(
+        vec!["lexer error: unexpected character '\\' at line 1, column 3", "unterminated list at line 2, column 1"],
+        errors.into_iter().map(|err| err.to_string()).collect::<Vec<_>>()
+    );
+}
+
+
@@ -89,11 +89,22 @@ impl<'a> SymbolTable<'a> {
         SymbolTable(Cow::default())
     }
 
+    /// A cheap copy of the table that borrows its symbols from this one.
+    pub(crate) fn borrowed(&self) -> SymbolTable<'_> {
+        SymbolTable(Cow::Borrowed(&self.0))
+    }
+
     pub fn contains(&self, symbol: &SymbolString) -> bool {
         self.0.binary_search(symbol).is_ok()
     }
 
-    pub fn add(&mut self, symbol: SymbolString<'a>) -> Result<(), Error> {
+    /// The first symbol in the table that begins with the given bytes.
+    pub(crate) fn first_with_prefix(&self, prefix: &[u8]) -> Option<&SymbolString<'a>> {
+        let index = self.0.partition_point(|symbol| *symbol.0 < *prefix);
+        self.0.get(index).filter(|symbol| symbol.0.starts_with(prefix))
+    }
+
+    pub fn add(&mut self, symbol: SymbolString<'a>) -> Result<(), Error<'a>> {
         let prefix_exists = match &symbol.0 {
             Cow::Borrowed(slice) => {
                 if slice.len() == 2 {
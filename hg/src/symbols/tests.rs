@@ -46,6 +46,19 @@ fn symbols_add_missing_prefix_err() {
     assert_eq!("missing prefix for [b':', b'?', b'%']", err.to_string());
 }
 
+#[test]
+fn symbols_first_with_prefix() {
+    let mut symbols = SymbolTable::empty();
+    symbols.add(SymbolString::try_from("::").unwrap()).unwrap();
+    symbols.add(SymbolString::try_from("::?").unwrap()).unwrap();
+    symbols.add(SymbolString::try_from("-=").unwrap()).unwrap();
+    assert_eq!(Some(&SymbolString::try_from("::").unwrap()), symbols.first_with_prefix(b":"));
+    assert_eq!(Some(&SymbolString::try_from("::?").unwrap()), symbols.first_with_prefix(b"::?"));
+    assert_eq!(Some(&SymbolString::try_from("-=").unwrap()), symbols.first_with_prefix(b"-"));
+    assert_eq!(None, symbols.first_with_prefix(b":::"));
+    assert_eq!(None, symbols.first_with_prefix(b"="));
+}
+
 const EXPECTED_SYMBOLS: &str = "!#$%&*+,-./:;<=>?@^`|~";
 
 #[test]
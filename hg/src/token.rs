@@ -1,5 +1,7 @@
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use crate::types::unqualified_type_name;
 
 #[derive(PartialEq, Eq, Clone)]
@@ -18,7 +20,7 @@ impl Display for Ascii {
 }
 
 #[derive(PartialEq, Eq, Clone)]
-pub struct AsciiSlice<'a>(pub &'a [u8]);
+pub struct AsciiSlice<'a>(pub Cow<'a, [u8]>);
 
 impl Debug for AsciiSlice<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -36,19 +38,125 @@ impl Debug for AsciiSlice<'_> {
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Token<'a> {
     Text(Cow<'a, str>),
+    /// Opens an interpolated text literal, such as `"Hello ${name}!"`. Only emitted when enabled
+    /// in the [`Options`](crate::lexer::Options); the literal's segments and embedded expressions
+    /// follow, up to the matching [`Token::InterpolationEnd`].
+    InterpolationBegin,
+    /// A literal segment of an interpolated text, with its escape sequences resolved.
+    TextSegment(Cow<'a, str>),
+    /// Opens an expression (`${`) embedded in an interpolated text. The expression comprises the
+    /// ordinary tokens up to the matching [`Token::EmbedEnd`].
+    EmbedBegin,
+    /// Closes an embedded expression (`}`).
+    EmbedEnd,
+    /// Closes an interpolated text literal.
+    InterpolationEnd,
     Character(char),
+    /// A byte string literal, such as `b"\x00\xff"`.
+    Bytes(Cow<'a, [u8]>),
+    /// A byte character literal, such as `b'a'`.
+    Byte(u8),
     Integer(u128),
+    /// An integer written with a radix prefix, such as `0xff`.
+    RadixInteger(u128, Radix),
+    /// An integer too large to fit in a `u128`.
+    BigInteger(BigInteger<'a>),
     Decimal(Decimal),
+    /// A decimal whose whole or fractional part is too large to fit in a `u128`. Also yielded by
+    /// scientific notation with a significand too large for a [`Decimal`], which is expanded by
+    /// its exponent (yielding a [`Token::Decimal`] instead if the result fits).
+    BigDecimal(BigDecimal<'a>),
+    /// A number in scientific notation whose significand fits in a [`Decimal`]. Its exponent,
+    /// like that of a big significand, is at most [`MAX_EXPONENT`](crate::lexer::MAX_EXPONENT)
+    /// in magnitude; a larger one is an error.
+    Scientific(Scientific),
+    /// A number with a directly attached leading sign. Only emitted when enabled in the
+    /// [`Options`](crate::lexer::Options); the boxed token is always numeric.
+    Signed(Sign, Box<Token<'a>>),
     Boolean(bool),
     Left(ListDelimiter),
     Right(ListDelimiter),
     Symbol(Ascii),
     ExtendedSymbol(AsciiSlice<'a>),
     Ident(Cow<'a, str>),
+    /// A comment, verbatim, including its delimiters. Only emitted when enabled in the
+    /// [`Comments`](crate::lexer::Comments) options.
+    Comment(Cow<'a, str>),
     Newline,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// A line terminator. Every terminator is lexed as a [`Token::Newline`]; the
+/// [`Tokeniser`](crate::lexer::Tokeniser) records the one a document used so that it may be
+/// written back the same way.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// `\r`
+    Cr,
+    /// U+0085 NEXT LINE
+    Nel,
+    /// U+2028 LINE SEPARATOR
+    LineSeparator,
+    /// U+2029 PARAGRAPH SEPARATOR
+    ParagraphSeparator,
+}
+
+impl LineEnding {
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+            LineEnding::Nel => "\u{85}",
+            LineEnding::LineSeparator => "\u{2028}",
+            LineEnding::ParagraphSeparator => "\u{2029}",
+        }
+    }
+}
+
+impl Display for LineEnding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Token<'_> {
+    /// Converts the token into one that owns all its data, copying any borrowed slices.
+    pub fn into_owned(self) -> Token<'static> {
+        match self {
+            Token::Text(str) => Token::Text(Cow::Owned(str.into_owned())),
+            Token::InterpolationBegin => Token::InterpolationBegin,
+            Token::TextSegment(str) => Token::TextSegment(Cow::Owned(str.into_owned())),
+            Token::EmbedBegin => Token::EmbedBegin,
+            Token::EmbedEnd => Token::EmbedEnd,
+            Token::InterpolationEnd => Token::InterpolationEnd,
+            Token::Character(char) => Token::Character(char),
+            Token::Bytes(bytes) => Token::Bytes(Cow::Owned(bytes.into_owned())),
+            Token::Byte(byte) => Token::Byte(byte),
+            Token::Integer(integer) => Token::Integer(integer),
+            Token::RadixInteger(integer, radix) => Token::RadixInteger(integer, radix),
+            Token::BigInteger(integer) => Token::BigInteger(integer.into_owned()),
+            Token::Decimal(decimal) => Token::Decimal(decimal),
+            Token::BigDecimal(decimal) => Token::BigDecimal(decimal.into_owned()),
+            Token::Scientific(scientific) => Token::Scientific(scientific),
+            Token::Signed(sign, token) => Token::Signed(sign, Box::new(token.into_owned())),
+            Token::Boolean(boolean) => Token::Boolean(boolean),
+            Token::Left(delimiter) => Token::Left(delimiter),
+            Token::Right(delimiter) => Token::Right(delimiter),
+            Token::Symbol(ascii) => Token::Symbol(ascii),
+            Token::ExtendedSymbol(AsciiSlice(bytes)) => Token::ExtendedSymbol(AsciiSlice(Cow::Owned(bytes.into_owned()))),
+            Token::Ident(str) => Token::Ident(Cow::Owned(str.into_owned())),
+            Token::Comment(str) => Token::Comment(Cow::Owned(str.into_owned())),
+            Token::Newline => Token::Newline,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum ListDelimiter {
     Paren,
     Brace,
@@ -56,20 +164,330 @@ pub enum ListDelimiter {
     Angle
 }
 
-/// A decimal in the form (whole part, fractional part, scale).
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+impl ListDelimiter {
+    /// The character that opens a list with this delimiter.
+    pub fn left(self) -> char {
+        match self {
+            ListDelimiter::Paren => '(',
+            ListDelimiter::Brace => '{',
+            ListDelimiter::Bracket => '[',
+            ListDelimiter::Angle => '<',
+        }
+    }
+
+    /// The character that closes a list with this delimiter.
+    pub fn right(self) -> char {
+        match self {
+            ListDelimiter::Paren => ')',
+            ListDelimiter::Brace => '}',
+            ListDelimiter::Bracket => ']',
+            ListDelimiter::Angle => '>',
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Sign {
+    Plus,
+    Minus
+}
+
+/// The base of a radix-prefixed integer literal.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Hexadecimal,
+}
+
+impl Radix {
+    #[inline]
+    pub fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Hexadecimal => 16,
+        }
+    }
+
+    #[inline]
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Radix::Binary => "0b",
+            Radix::Octal => "0o",
+            Radix::Hexadecimal => "0x",
+        }
+    }
+
+    /// Formats an integer in this radix, including the prefix.
+    pub fn format(self, value: u128) -> String {
+        match self {
+            Radix::Binary => format!("0b{value:b}"),
+            Radix::Octal => format!("0o{value:o}"),
+            Radix::Hexadecimal => format!("0x{value:x}"),
+        }
+    }
+}
+
+/// A character in the digits of a [`BigInteger`] or a [`BigDecimal`] that is not a digit in its
+/// radix.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid digit '{0}'")]
+pub struct InvalidDigit(pub char);
+
+/// An integer too large to fit in a `u128`, retained as its validated digits without the radix
+/// prefix or digit separators. The radix is absent for a decimal integer.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BigInteger<'a>(pub Cow<'a, str>, pub Option<Radix>);
+
+impl BigInteger<'_> {
+    pub fn into_owned(self) -> BigInteger<'static> {
+        BigInteger(Cow::Owned(self.0.into_owned()), self.1)
+    }
+
+    /// The magnitude in big-endian byte order, without leading zero bytes, or an error if the
+    /// digits are invalid in the radix.
+    pub fn to_be_bytes(&self) -> Result<Vec<u8>, InvalidDigit> {
+        let base = self.1.map_or(10, Radix::base) as u64;
+        let mut limbs: Vec<u32> = vec![]; // little-endian
+        for char in self.0.chars() {
+            let mut carry = char.to_digit(base as u32).ok_or(InvalidDigit(char))? as u64;
+            for limb in &mut limbs {
+                let product = *limb as u64 * base + carry;
+                *limb = product as u32;
+                carry = product >> 32;
+            }
+            if carry != 0 {
+                limbs.push(carry as u32);
+            }
+        }
+        Ok(limbs.iter().rev().flat_map(|limb| limb.to_be_bytes()).skip_while(|&byte| byte == 0).collect())
+    }
+}
+
+impl Display for BigInteger<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.1.map_or("", Radix::prefix), self.0)
+    }
+}
+
+impl TryFrom<&BigInteger<'_>> for f64 {
+    type Error = InvalidDigit;
+
+    fn try_from(integer: &BigInteger<'_>) -> Result<Self, Self::Error> {
+        let bytes = integer.to_be_bytes()?;
+        let (top, rest) = bytes.split_at(bytes.len().min(16));
+        let top = top.iter().fold(0_u128, |acc, &byte| acc << 8 | byte as u128);
+        // the top bytes hold well over 53 significant bits; folding any remaining nonzero bits
+        // into the least significant one is enough for correct rounding
+        let sticky = rest.iter().any(|&byte| byte != 0) as u128;
+        Ok((top | sticky) as f64 * 2_f64.powi(rest.len() as i32 * 8))
+    }
+}
+
+/// A decimal whose whole or fractional part is too large to fit in a `u128`, in the form (whole
+/// digits, fractional digits). Both are validated and exclude digit separators; the scale is the
+/// number of fractional digits.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BigDecimal<'a>(pub Cow<'a, str>, pub Cow<'a, str>);
+
+impl BigDecimal<'_> {
+    pub fn into_owned(self) -> BigDecimal<'static> {
+        BigDecimal(Cow::Owned(self.0.into_owned()), Cow::Owned(self.1.into_owned()))
+    }
+}
+
+impl Display for BigDecimal<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.0, self.1)
+    }
+}
+
+impl TryFrom<&BigDecimal<'_>> for f64 {
+    type Error = InvalidDigit;
+
+    fn try_from(decimal: &BigDecimal<'_>) -> Result<Self, Self::Error> {
+        match decimal.0.chars().chain(decimal.1.chars()).find(|char| !char.is_ascii_digit()) {
+            None => Ok(format!("0{decimal}").parse().unwrap()), // the leading zero admits empty parts
+            Some(char) => Err(InvalidDigit(char))
+        }
+    }
+}
+
+/// A decimal in the form (whole part, fractional part, scale), denoting `whole + fractional ×
+/// 10^-scale`. The fractional part must be less than `10^scale`, as it is for lexed decimals.
+///
+/// Comparison, equality and hashing are numeric, so that `1.50` equals `1.5` and sorts above
+/// `1.25`.
+#[derive(Debug, Clone, Copy)]
 pub struct Decimal(pub u128, pub u128, pub u8);
 
+impl Decimal {
+    /// The numerically equal decimal with the trailing zeros removed from the fractional part.
+    pub fn normalise(self) -> Self {
+        let Decimal(whole, mut fractional, mut scale) = self;
+        while scale > 0 && fractional % 10 == 0 {
+            fractional /= 10;
+            scale -= 1;
+        }
+        Decimal(whole, fractional, scale)
+    }
+
+    /// Adds two decimals, yielding the greater of the two scales, or `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let (scale, lhs, rhs) = align(self, other)?;
+        let fractional = lhs.checked_add(rhs)?;
+        let (carry, fractional) = match pow10(scale) {
+            Some(unit) if fractional >= unit => (1, fractional - unit),
+            _ => (0, fractional)
+        };
+        let whole = self.0.checked_add(other.0)?.checked_add(carry)?;
+        Some(Decimal(whole, fractional, scale))
+    }
+
+    /// Subtracts a decimal, yielding the greater of the two scales, or `None` if the result is
+    /// negative or cannot be represented.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        let (scale, lhs, rhs) = align(self, other)?;
+        let whole = self.0.checked_sub(other.0)?;
+        if lhs >= rhs {
+            Some(Decimal(whole, lhs - rhs, scale))
+        } else {
+            Some(Decimal(whole.checked_sub(1)?, pow10(scale)? - rhs + lhs, scale))
+        }
+    }
+
+    /// Multiplies two decimals, yielding the sum of the two scales, or `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let (Decimal(lhs_whole, lhs_fractional, lhs_scale), Decimal(rhs_whole, rhs_fractional, rhs_scale)) = (self, other);
+        let scale = lhs_scale.checked_add(rhs_scale)?;
+        let (unit, lhs_unit, rhs_unit) = (pow10(scale)?, pow10(lhs_scale)?, pow10(rhs_scale)?);
+
+        // each whole part times the other's fractional part contributes to both parts of the result
+        let lhs_cross = lhs_whole.checked_mul(rhs_fractional)?;
+        let rhs_cross = rhs_whole.checked_mul(lhs_fractional)?;
+        let fractional = (lhs_cross % rhs_unit * lhs_unit)
+            .checked_add(rhs_cross % lhs_unit * rhs_unit)?
+            .checked_add(lhs_fractional * rhs_fractional)?;
+        let whole = lhs_whole.checked_mul(rhs_whole)?
+            .checked_add(lhs_cross / rhs_unit)?
+            .checked_add(rhs_cross / lhs_unit)?
+            .checked_add(fractional / unit)?;
+        Some(Decimal(whole, fractional % unit, scale))
+    }
+}
+
+#[inline]
+fn pow10(exponent: u8) -> Option<u128> {
+    10_u128.checked_pow(exponent as u32)
+}
+
+/// Brings the fractional parts of two decimals to their common scale.
+#[inline]
+fn align(lhs: Decimal, rhs: Decimal) -> Option<(u8, u128, u128)> {
+    let scale = lhs.2.max(rhs.2);
+    let lhs_fractional = lhs.1.checked_mul(pow10(scale - lhs.2)?)?;
+    let rhs_fractional = rhs.1.checked_mul(pow10(scale - rhs.2)?)?;
+    Some((scale, lhs_fractional, rhs_fractional))
+}
+
+/// Compares `lhs × 10^exponent` with `rhs`.
+#[inline]
+fn cmp_scaled(lhs: u128, exponent: u8, rhs: u128) -> Ordering {
+    if lhs == 0 {
+        return 0.cmp(&rhs)
+    }
+    match pow10(exponent).and_then(|unit| lhs.checked_mul(unit)) {
+        Some(scaled) => scaled.cmp(&rhs),
+        None => Ordering::Greater // beyond the range of rhs
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0).then_with(|| {
+            if self.2 <= other.2 {
+                cmp_scaled(self.1, other.2 - self.2, other.1)
+            } else {
+                cmp_scaled(other.1, self.2 - other.2, self.1).reverse()
+            }
+        })
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Decimal(whole, fractional, scale) = self.normalise();
+        whole.hash(state);
+        fractional.hash(state);
+        scale.hash(state);
+    }
+}
+
+/// Formats the decimal canonically, omitting trailing fractional zeros but retaining at least one
+/// fractional digit, as in `7.0`.
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.normalise() {
+            Decimal(whole, _, 0) => write!(f, "{whole}.0"),
+            Decimal(whole, fractional, scale) => write!(f, "{whole}.{fractional:0>scale$}", scale = scale as usize)
+        }
+    }
+}
+
 impl From<Decimal> for f64 {
     fn from(decimal: Decimal) -> Self {
-        let Decimal(whole, fractional, scale) = decimal;
-        whole as f64 + fractional as f64 / 10_f64.powi(scale as i32)
+        // formatting and reparsing yields a correctly rounded result
+        decimal.to_string().parse().unwrap()
+    }
+}
+
+/// A number in scientific notation in the form (significand, base-10 exponent). A significand
+/// written without a fractional part, as in `1e10`, has a scale of zero.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Scientific(pub Decimal, pub i32);
+
+impl From<Scientific> for f64 {
+    fn from(scientific: Scientific) -> Self {
+        let Scientific(significand, exponent) = scientific;
+        // formatting and reparsing yields a correctly rounded result
+        format!("{significand}e{exponent}").parse().unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::token::{Ascii, Decimal};
+    use std::borrow::Cow;
+    use std::collections::HashSet;
+    use crate::token::{Ascii, AsciiSlice, BigDecimal, BigInteger, Decimal, InvalidDigit, LineEnding, ListDelimiter, Radix, Scientific, Token};
+
+    #[test]
+    fn line_ending_display() {
+        for (line_ending, expect) in [
+            (LineEnding::Lf, "\n"),
+            (LineEnding::CrLf, "\r\n"),
+            (LineEnding::Cr, "\r"),
+            (LineEnding::Nel, "\u{85}"),
+            (LineEnding::LineSeparator, "\u{2028}"),
+            (LineEnding::ParagraphSeparator, "\u{2029}"),
+        ] {
+            assert_eq!(expect, line_ending.to_string(), "for {line_ending:?}");
+        }
+    }
 
     #[test]
     fn ascii_debug() {
@@ -83,6 +501,92 @@ mod tests {
         assert_eq!("a", format!("{ascii}"));
     }
 
+    #[test]
+    fn token_into_owned() {
+        let token = Token::ExtendedSymbol(AsciiSlice(b"::".into()));
+        let owned = token.clone().into_owned();
+        assert_eq!(token, owned);
+        assert!(matches!(owned, Token::ExtendedSymbol(AsciiSlice(Cow::Owned(_)))));
+
+        let token = Token::Text("hello".into());
+        let owned = token.clone().into_owned();
+        assert_eq!(token, owned);
+        assert!(matches!(owned, Token::Text(Cow::Owned(_))));
+    }
+
+    #[test]
+    fn list_delimiter_chars() {
+        assert_eq!(('(', ')'), (ListDelimiter::Paren.left(), ListDelimiter::Paren.right()));
+        assert_eq!(('{', '}'), (ListDelimiter::Brace.left(), ListDelimiter::Brace.right()));
+        assert_eq!(('[', ']'), (ListDelimiter::Bracket.left(), ListDelimiter::Bracket.right()));
+        assert_eq!(('<', '>'), (ListDelimiter::Angle.left(), ListDelimiter::Angle.right()));
+    }
+
+    #[test]
+    fn radix_format() {
+        assert_eq!("0b1010", Radix::Binary.format(10));
+        assert_eq!("0o755", Radix::Octal.format(0o755));
+        assert_eq!("0xff", Radix::Hexadecimal.format(255));
+        assert_eq!("0x0", Radix::Hexadecimal.format(0));
+    }
+
+    #[test]
+    fn f64_from_scientific() {
+        assert_eq!(1e10, f64::from(Scientific(Decimal(1, 0, 0), 10)));
+        assert_eq!(1.5e-3, f64::from(Scientific(Decimal(1, 5, 1), -3)));
+        assert_eq!(0.05e2, f64::from(Scientific(Decimal(0, 5, 2), 2)));
+        assert_eq!(0.1e-300, f64::from(Scientific(Decimal(0, 1, 1), -300)));
+        assert_eq!(f64::INFINITY, f64::from(Scientific(Decimal(1, 0, 0), 400)));
+    }
+
+    #[test]
+    fn big_integer_to_be_bytes() {
+        let max = BigInteger(u128::MAX.to_string().into(), None);
+        assert_eq!(vec![0xff; 16], max.to_be_bytes().unwrap());
+
+        let two_pow_128 = BigInteger("340282366920938463463374607431768211456".into(), None);
+        assert_eq!([&[1], &[0; 16][..]].concat(), two_pow_128.to_be_bytes().unwrap());
+
+        let hex = BigInteger("1000000000000000000000000000000ff".into(), Some(Radix::Hexadecimal));
+        assert_eq!([&[1], &[0; 15][..], &[0xff]].concat(), hex.to_be_bytes().unwrap());
+
+        let binary = BigInteger(format!("1{}", "0".repeat(136)).into(), Some(Radix::Binary));
+        assert_eq!([&[1], &[0; 17][..]].concat(), binary.to_be_bytes().unwrap());
+
+        let invalid = BigInteger("12a".into(), None);
+        assert_eq!(Err(InvalidDigit('a')), invalid.to_be_bytes());
+        assert_eq!(Err(InvalidDigit('a')), f64::try_from(&invalid));
+    }
+
+    #[test]
+    fn big_integer_display() {
+        assert_eq!("0xabc", BigInteger("abc".into(), Some(Radix::Hexadecimal)).to_string());
+        assert_eq!("123", BigInteger("123".into(), None).to_string());
+    }
+
+    #[test]
+    fn f64_from_big_integer() {
+        assert_eq!(2_f64.powi(128), f64::try_from(&BigInteger("340282366920938463463374607431768211456".into(), None)).unwrap());
+        assert_eq!(1e40, f64::try_from(&BigInteger(format!("1{}", "0".repeat(40)).into(), None)).unwrap());
+        assert_eq!(2_f64.powi(256), f64::try_from(&BigInteger(format!("1{}", "0".repeat(64)).into(), Some(Radix::Hexadecimal))).unwrap());
+
+        // 2^200 + 2^147 lies exactly halfway between two doubles and rounds to even, whereas the
+        // slightest excess beyond the halfway point rounds up
+        let halfway = format!("1{}8{}", "0".repeat(13), "0".repeat(36));
+        assert_eq!(2_f64.powi(200), f64::try_from(&BigInteger(halfway.into(), Some(Radix::Hexadecimal))).unwrap());
+        let above_halfway = format!("1{}8{}1", "0".repeat(13), "0".repeat(35));
+        assert_eq!(2_f64.powi(200) + 2_f64.powi(148), f64::try_from(&BigInteger(above_halfway.into(), Some(Radix::Hexadecimal))).unwrap());
+        assert_eq!(f64::INFINITY, f64::try_from(&BigInteger("f".repeat(300).into(), Some(Radix::Hexadecimal))).unwrap());
+    }
+
+    #[test]
+    fn f64_from_big_decimal() {
+        assert_eq!(1e40, f64::try_from(&BigDecimal(format!("1{}", "0".repeat(40)).into(), "0".into())).unwrap());
+        assert_eq!(0.1, f64::try_from(&BigDecimal("0".into(), format!("1{}", "0".repeat(40)).into())).unwrap());
+        assert_eq!(0.0, f64::try_from(&BigDecimal("".into(), "".into())).unwrap());
+        assert_eq!(Err(InvalidDigit('e')), f64::try_from(&BigDecimal("1e5".into(), "0".into())));
+    }
+
     #[test]
     fn f64_from_decimal() {
         assert_eq!(7.0, f64::from(Decimal(7, 0, 1)));
@@ -94,5 +598,67 @@ mod tests {
         assert_eq!(7.01, f64::from(Decimal(7, 10, 3)));
         assert_eq!(7.012, f64::from(Decimal(7, 12, 3)));
         assert_eq!(7.123, f64::from(Decimal(7, 123, 3)));
+        assert_eq!(68.9929299, f64::from(Decimal(68, 9929299, 7)));
+        assert_eq!(1.7307776560678618, f64::from(Decimal(1, 73077765606786192, 17)));
+    }
+
+    #[test]
+    fn decimal_eq() {
+        assert_eq!(Decimal(1, 5, 1), Decimal(1, 50, 2));
+        assert_eq!(Decimal(7, 0, 0), Decimal(7, 0, 3));
+        assert_ne!(Decimal(1, 5, 1), Decimal(1, 5, 2));
+        assert_ne!(Decimal(1, 5, 1), Decimal(2, 5, 1));
+
+        let set = HashSet::from([Decimal(1, 5, 1), Decimal(1, 50, 2), Decimal(1, 500, 3)]);
+        assert_eq!(1, set.len());
+    }
+
+    #[test]
+    fn decimal_ord() {
+        assert!(Decimal(1, 5, 1) > Decimal(1, 25, 2));
+        assert!(Decimal(1, 5, 1) < Decimal(1, 51, 2));
+        assert!(Decimal(1, 9, 1) < Decimal(2, 0, 1));
+        assert!(Decimal(0, 0, 0) < Decimal(0, 1, 200));
+        assert!(Decimal(0, 1, 1) > Decimal(0, u128::MAX, 200));
+
+        let mut decimals = vec![Decimal(1, 5, 1), Decimal(1, 25, 2), Decimal(0, 999, 3), Decimal(1, 3, 1)];
+        decimals.sort();
+        assert_eq!(vec![Decimal(0, 999, 3), Decimal(1, 25, 2), Decimal(1, 3, 1), Decimal(1, 5, 1)], decimals);
+    }
+
+    #[test]
+    fn decimal_display() {
+        assert_eq!("1.5", Decimal(1, 50, 2).to_string());
+        assert_eq!("1.05", Decimal(1, 5, 2).to_string());
+        assert_eq!("7.0", Decimal(7, 0, 3).to_string());
+        assert_eq!("7.0", Decimal(7, 0, 0).to_string());
+        assert_eq!("0.001", Decimal(0, 1, 3).to_string());
+    }
+
+    #[test]
+    fn decimal_checked_add() {
+        assert_eq!(Some(Decimal(0, 3, 1)), Decimal(0, 1, 1).checked_add(Decimal(0, 2, 1)));
+        assert_eq!(Some(Decimal(3, 25, 2)), Decimal(1, 5, 1).checked_add(Decimal(1, 75, 2)));
+        assert_eq!(Some(Decimal(2, 0, 1)), Decimal(1, 5, 1).checked_add(Decimal(0, 5, 1)));
+        assert_eq!(None, Decimal(u128::MAX, 5, 1).checked_add(Decimal(0, 5, 1)));
+    }
+
+    #[test]
+    fn decimal_checked_sub() {
+        assert_eq!(Some(Decimal(0, 1, 1)), Decimal(0, 3, 1).checked_sub(Decimal(0, 2, 1)));
+        assert_eq!(Some(Decimal(0, 75, 2)), Decimal(2, 5, 1).checked_sub(Decimal(1, 75, 2)));
+        assert_eq!(Some(Decimal(0, 0, 1)), Decimal(1, 5, 1).checked_sub(Decimal(1, 5, 1)));
+        assert_eq!(None, Decimal(1, 5, 1).checked_sub(Decimal(1, 6, 1)));
+        assert_eq!(None, Decimal(1, 5, 1).checked_sub(Decimal(2, 0, 1)));
+    }
+
+    #[test]
+    fn decimal_checked_mul() {
+        assert_eq!(Some(Decimal(0, 2, 2)), Decimal(0, 1, 1).checked_mul(Decimal(0, 2, 1)));
+        assert_eq!(Some(Decimal(3, 75, 2)), Decimal(1, 5, 1).checked_mul(Decimal(2, 5, 1)));
+        assert_eq!(Some(Decimal(246, 900, 3)), Decimal(123, 45, 2).checked_mul(Decimal(2, 0, 1)));
+        assert_eq!(Some(Decimal(9, 8901, 4)), Decimal(9, 99, 2).checked_mul(Decimal(0, 99, 2)));
+        assert_eq!(None, Decimal(u128::MAX, 0, 1).checked_mul(Decimal(2, 0, 1)));
+        assert_eq!(None, Decimal(0, 1, 20).checked_mul(Decimal(0, 1, 20)));
     }
 }
\ No newline at end of file
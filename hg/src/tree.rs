@@ -1,11 +1,17 @@
+use std::borrow::Cow;
 use crate::metadata::Metadata;
-use crate::token::Token;
+use crate::token::{ListDelimiter, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Node<'a> {
     Raw(Token<'a>, Metadata),
-    List(Vec<Verse<'a>>, Metadata),
+    List(ListDelimiter, Vec<Verse<'a>>, Metadata),
     Relation(Box<Node<'a>>, Phrase<'a>, Metadata),
+    /// An interpolated text, comprising its literal segments and embedded expressions in order.
+    Interpolation(Vec<Part<'a>>, Metadata),
+    /// A placeholder for a missing part of a construct, inserted by a recovering parse (see
+    /// [`parse_recovering`](crate::parser::parse_recovering)).
+    Error(Metadata),
 }
 
 impl Node<'_> {
@@ -13,8 +19,28 @@ impl Node<'_> {
     pub fn metadata(&self) -> &Metadata {
         match self {
             Node::Raw(_, metadata) => metadata,
-            Node::List(_, metadata) => metadata,
+            Node::List(_, _, metadata) => metadata,
             Node::Relation(_, _, metadata) => metadata,
+            Node::Interpolation(_, metadata) => metadata,
+            Node::Error(metadata) => metadata,
+        }
+    }
+}
+
+/// A part of an interpolated text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Part<'a> {
+    Text(Cow<'a, str>, Metadata),
+    /// An embedded expression, excluding its `${` and `}` delimiters.
+    Expression(Phrase<'a>),
+}
+
+impl Part<'_> {
+    #[inline]
+    pub fn metadata(&self) -> &Metadata {
+        match self {
+            Part::Text(_, metadata) => metadata,
+            Part::Expression(phrase) => phrase.metadata(),
         }
     }
 }
@@ -69,9 +95,7 @@ impl<'a> Verse<'a> {
         if self.0.is_empty() {
             Metadata::unspecified()
         } else {
-            let start = self.0[0].metadata().start.clone();
-            let end = self.0[self.0.len() - 1].metadata().end.clone();
-            Metadata { start, end }
+            self.0[0].metadata().merge(self.0[self.0.len() - 1].metadata())
         }
     }
 }
@@ -88,7 +112,7 @@ macro_rules! phrase {
         $crate::tree::Phrase::new(Vec::new(), $crate::metadata::Metadata::unspecified())
     );
     ($($x:expr),+ $(,)?) => (
-        $crate::tree::Phrase::new((vec![$($x),+]), $crate::metadata::Metadata::unspecified())
+        $crate::tree::Phrase::new(vec![$($x),+], $crate::metadata::Metadata::unspecified())
     );
 }
 
@@ -98,14 +122,24 @@ macro_rules! verse {
         $crate::tree::Verse::new(Vec::new())
     );
     ($($x:expr),+ $(,)?) => (
-        $crate::tree::Verse::new((vec![$($x),+]))
+        $crate::tree::Verse::new(vec![$($x),+])
+    );
+}
+
+#[macro_export]
+macro_rules! list {
+    ($delimiter:expr) => (
+        $crate::tree::Node::List($delimiter, Vec::new(), $crate::metadata::Metadata::unspecified())
+    );
+    ($delimiter:expr; $($x:expr),+ $(,)?) => (
+        $crate::tree::Node::List($delimiter, vec![$($x),+], $crate::metadata::Metadata::unspecified())
     );
 }
 
 #[cfg(test)]
 mod tests {
     use crate::metadata::Metadata;
-    use crate::token::Token;
+    use crate::token::{ListDelimiter, Token};
     use crate::tree::{Node, Phrase, Verse};
 
     #[test]
@@ -145,4 +179,16 @@ mod tests {
         let vec: Vec<_> = verse.into();
         assert_eq!(vec![Phrase(vec![Node::Raw(Token::Integer(1), Metadata::unspecified())], Metadata::unspecified())], vec);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn empty_list() {
+        let list = list!(ListDelimiter::Bracket);
+        assert_eq!(Node::List(ListDelimiter::Bracket, vec![], Metadata::unspecified()), list);
+    }
+
+    #[test]
+    fn nonempty_list() {
+        let list = list!(ListDelimiter::Brace; verse![phrase![Node::Raw(Token::Integer(1), Metadata::unspecified())]]);
+        assert_eq!(Node::List(ListDelimiter::Brace, vec![Verse(vec![Phrase(vec![Node::Raw(Token::Integer(1), Metadata::unspecified())], Metadata::unspecified())])], Metadata::unspecified()), list);
+    }
+}
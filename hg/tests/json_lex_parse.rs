@@ -1,20 +1,24 @@
-use hg::lexer::Tokeniser;
+use hg::lexer::stream::StreamTokeniser;
+use hg::lexer::{Fragment, Options, Tokeniser};
 use hg::metadata::Metadata;
 use hg::parser::parse;
 use hg::symbols::SymbolTable;
-use hg::token::Token::{Boolean, Decimal, Ident, Integer, Symbol, Text};
-use hg::token::{Ascii, Token};
+use hg::token::Token::{Boolean, Decimal, Ident, Integer, Signed, Symbol, Text};
+use hg::token::ListDelimiter::{Brace, Bracket};
+use hg::token::{Ascii, Sign, Token};
 use hg::tree::Node::{Relation, List, Raw};
 use hg::tree::{Node, Phrase, Verse};
-use hg::{lexer, token, verse};
+use hg::{token, verse};
 use std::iter::Map;
 use std::vec::IntoIter;
 
-fn tok_ok(str: &str) -> Vec<Token> {
+fn tok_ok(str: &str) -> Vec<Token<'_>> {
     Tokeniser::new(str, SymbolTable::default()).map(Result::unwrap).map(|(token, _)| token).collect()
 }
 
-fn without_metadata(tokens: Vec<Token>) -> Map<IntoIter<Token>, fn(Token) -> Result<(Token, Metadata), Box<lexer::Error>>> {
+type FragmentIter<'a> = Map<IntoIter<Token<'a>>, fn(Token<'a>) -> Fragment<'a>>;
+
+fn without_metadata<'a>(tokens: Vec<Token<'a>>) -> FragmentIter<'a> {
     tokens.into_iter().map(|token| Ok((token, Metadata::unspecified())))
 }
 
@@ -22,7 +26,7 @@ fn parse_ok(tokens: Vec<Token>) -> Option<Verse> {
     parse(without_metadata(tokens)).unwrap()
 }
 
-fn string(value: &str) -> Vec<Node> {
+fn string(value: &str) -> Vec<Node<'_>> {
     vec![Raw(Text(value.into()), Metadata::unspecified())]
 }
 
@@ -52,6 +56,13 @@ fn negative(value: impl Into<Vec<Node<'static>>>) -> Vec<Node<'static>> {
     concat
 }
 
+fn signed(sign: Sign, value: Vec<Node<'static>>) -> Vec<Node<'static>> {
+    value.into_iter().map(|node| match node {
+        Raw(token, metadata) => Raw(Signed(sign, Box::new(token)), metadata),
+        _ => unreachable!()
+    }).collect()
+}
+
 fn key_value(key: &'static str, value: Vec<Node<'static>>) -> Vec<Node<'static>> {
     vec![Relation(
         Box::new(Raw(Text(key.into()), Metadata::unspecified())),
@@ -72,7 +83,7 @@ impl ArrayBuilder {
 impl From<ArrayBuilder> for Vec<Node<'static>> {
     fn from(array_builder: ArrayBuilder) -> Self {
         let verses = array_builder.0.into_iter().map(|element| verse![Phrase::new(element, Metadata::unspecified())]).collect();
-        vec![List(verses, Metadata::unspecified())]
+        vec![List(Bracket, verses, Metadata::unspecified())]
     }
 }
 
@@ -91,7 +102,7 @@ impl ObjectBuilder {
 impl From<ObjectBuilder> for Vec<Node<'static>> {
     fn from(object_builder: ObjectBuilder) -> Self {
         let verses = object_builder.0.into_iter().map(|(key, value)| verse![Phrase::new(key_value(key, value), Metadata::unspecified())]).collect();
-        vec![List(verses, Metadata::unspecified())]
+        vec![List(Brace, verses, Metadata::unspecified())]
     }
 }
 
@@ -113,36 +124,34 @@ fn root(node: impl Into<Vec<Node<'static>>>) -> Verse<'static> {
     verse![Phrase::new(node.into(), Metadata::unspecified())]
 }
 
-#[test]
-fn multilevel_json() {
-    let str = r#"{
-        "key1": "value1",
-        "key2": 1234,
-        "key3": 1234.5678,
-        "key4": -345,
-        "key5": true,
-        "key6": null,
-        "emptyArray": [
-        ],
-        "employees": [
-            {
-                "id": 1,
-                "details": {"name": "John Wick", "age": 42, "dogOwner": true}
-            },
-            {
-                "id": 2,
-                "details": {"name": "Max Payne", "age": 39, "dogOwner": false}
-            }
-        ]
-    }"#;
-    let tokens = tok_ok(str);
-    let verse = parse_ok(tokens);
-    assert_eq!(root(
+const MULTILEVEL_JSON: &str = r#"{
+    "key1": "value1",
+    "key2": 1234,
+    "key3": 1234.5678,
+    "key4": -345,
+    "key5": true,
+    "key6": null,
+    "emptyArray": [
+    ],
+    "employees": [
+        {
+            "id": 1,
+            "details": {"name": "John Wick", "age": 42, "dogOwner": true}
+        },
+        {
+            "id": 2,
+            "details": {"name": "Max Payne", "age": 39, "dogOwner": false}
+        }
+    ]
+}"#;
+
+fn multilevel_json_expected(key4: Vec<Node<'static>>) -> Verse<'static> {
+    root(
         object()
             .key("key1").value(string("value1"))
             .key("key2").value(integer(1234))
             .key("key3").value(decimal(1234, 5678, 4))
-            .key("key4").value(negative(integer(345)))
+            .key("key4").value(key4)
             .key("key5").value(boolean(true))
             .key("key6").value(null())
             .key("emptyArray").value(array())
@@ -164,5 +173,30 @@ fn multilevel_json() {
                     )
                 )
             )
-    ), verse.unwrap());
-}
\ No newline at end of file
+    )
+}
+
+#[test]
+fn multilevel_json() {
+    let tokens = tok_ok(MULTILEVEL_JSON);
+    let verse = parse_ok(tokens);
+    assert_eq!(multilevel_json_expected(negative(integer(345))), verse.unwrap());
+}
+
+#[test]
+fn multilevel_json_signed_numbers() {
+    let options = Options {
+        signed_numbers: true,
+        ..Options::default()
+    };
+    let tokens = Tokeniser::with_options(MULTILEVEL_JSON, SymbolTable::default(), options).map(Result::unwrap).map(|(token, _)| token).collect();
+    let verse = parse_ok(tokens);
+    assert_eq!(multilevel_json_expected(signed(Sign::Minus, integer(345))), verse.unwrap());
+}
+
+#[test]
+fn multilevel_json_streamed() {
+    let expected = parse(Tokeniser::new(MULTILEVEL_JSON, SymbolTable::default())).unwrap();
+    let streamed = parse(StreamTokeniser::new(MULTILEVEL_JSON.as_bytes(), SymbolTable::default())).unwrap();
+    assert_eq!(expected, streamed);
+}